@@ -0,0 +1,161 @@
+use aoc_core::{ParseError, Source};
+use std::cmp;
+
+pub type Mass = i64;
+
+/// Fuel required by a mass: `mass / divisor - subtrahend`, rounded down and never negative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formula {
+    divisor: Mass,
+    subtrahend: Mass,
+}
+
+impl Default for Formula {
+    fn default() -> Self {
+        Self {
+            divisor: 3,
+            subtrahend: 2,
+        }
+    }
+}
+
+impl Formula {
+    /// Returns `None` when the fuel would not be lighter than its mass,
+    /// in which case the recursive fuel never converges
+    pub fn new(divisor: Mass, subtrahend: Mass) -> Option<Self> {
+        if divisor < 1 || subtrahend < 0 || (divisor == 1 && subtrahend == 0) {
+            None
+        } else {
+            Some(Self {
+                divisor,
+                subtrahend,
+            })
+        }
+    }
+
+    pub fn divisor(&self) -> Mass {
+        self.divisor
+    }
+
+    pub fn subtrahend(&self) -> Mass {
+        self.subtrahend
+    }
+
+    pub fn fuel(&self, mass: Mass) -> Mass {
+        cmp::max(mass / self.divisor - self.subtrahend, 0)
+    }
+
+    /// Fuel for the mass, plus the fuel for that fuel, until no more fuel is required
+    pub fn recursive_fuel(&self, mass: Mass) -> Mass {
+        let mut sum = 0;
+        let mut fuel = self.fuel(mass);
+        while fuel > 0 {
+            sum += fuel;
+            fuel = self.fuel(fuel);
+        }
+        sum
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleFuel {
+    pub mass: Mass,
+    pub fuel: Mass,
+    pub recursive_fuel: Mass,
+}
+
+pub fn parse_masses(raw: &str) -> Result<Vec<Mass>, ParseError> {
+    let source = Source::new(raw);
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| source.parse(line.trim(), "mass"))
+        .collect()
+}
+
+pub fn breakdown(masses: &[Mass], formula: &Formula) -> Vec<ModuleFuel> {
+    masses
+        .iter()
+        .map(|&mass| ModuleFuel {
+            mass,
+            fuel: formula.fuel(mass),
+            recursive_fuel: formula.recursive_fuel(mass),
+        })
+        .collect()
+}
+
+pub fn total_fuel(masses: &[Mass], formula: &Formula) -> Mass {
+    masses.iter().map(|&mass| formula.fuel(mass)).sum()
+}
+
+pub fn total_recursive_fuel(masses: &[Mass], formula: &Formula) -> Mass {
+    masses
+        .iter()
+        .map(|&mass| formula.recursive_fuel(mass))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_fuel() {
+        let formula = Formula::default();
+        assert_eq!(formula.fuel(12), 2);
+        assert_eq!(formula.fuel(14), 2);
+        assert_eq!(formula.fuel(1969), 654);
+        assert_eq!(formula.fuel(100756), 33583);
+        assert_eq!(formula.fuel(2), 0);
+    }
+
+    #[test]
+    fn test_recursive_fuel() {
+        let formula = Formula::default();
+        assert_eq!(formula.recursive_fuel(14), 2);
+        assert_eq!(formula.recursive_fuel(1969), 966);
+        assert_eq!(formula.recursive_fuel(100756), 50346);
+    }
+
+    #[test]
+    fn test_formula() {
+        assert_eq!(Formula::new(3, 2), Some(Formula::default()));
+        assert_eq!(Formula::new(1, 0), None);
+        assert_eq!(Formula::new(0, 2), None);
+        let formula = Formula::new(2, 0).unwrap();
+        // 100 -> 50 -> 25 -> 12 -> 6 -> 3 -> 1 -> 0
+        assert_eq!(formula.recursive_fuel(100), 97);
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let modules = breakdown(&[12, 1969], &Formula::default());
+        assert_eq!(
+            modules,
+            [
+                ModuleFuel {
+                    mass: 12,
+                    fuel: 2,
+                    recursive_fuel: 2
+                },
+                ModuleFuel {
+                    mass: 1969,
+                    fuel: 654,
+                    recursive_fuel: 966
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_p1() {
+        let masses = parse_masses(&aoc_input::read(2019, 1, Variant::Input).unwrap()).unwrap();
+        assert_eq!(total_fuel(&masses, &Formula::default()), 3270338);
+    }
+
+    #[test]
+    fn test_p2() {
+        let masses = parse_masses(&aoc_input::read(2019, 1, Variant::Input).unwrap()).unwrap();
+        assert_eq!(total_recursive_fuel(&masses, &Formula::default()), 4902650);
+    }
+}
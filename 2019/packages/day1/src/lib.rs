@@ -0,0 +1,18 @@
+pub mod fuel;
+
+use aoc_core::ParseError;
+use fuel::{Formula, Mass};
+
+pub fn process(raw: &str) -> Result<Vec<Mass>, ParseError> {
+    fuel::parse_masses(raw)
+}
+
+pub fn p1(input: &[Mass]) -> Mass {
+    fuel::total_fuel(input, &Formula::default())
+}
+
+pub fn p2(input: &[Mass]) -> Mass {
+    fuel::total_recursive_fuel(input, &Formula::default())
+}
+
+aoc_core::solution!(Day1, 2019, 1, Vec<Mass>);
@@ -1,22 +1,89 @@
-use std::cmp;
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader};
+use day1::fuel::{self, Formula};
+use std::env;
+use std::fs;
+use std::io::{self, prelude::*};
+
+const USAGE: &str = "Usage: day1 [--breakdown] [--divisor N] [--subtrahend N] [PATH|-]
+
+Reads the module masses from PATH, or from stdin when PATH is `-` or omitted.";
+
+struct Options {
+    path: Option<String>,
+    breakdown: bool,
+    formula: Formula,
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{}\n\n{}", message, USAGE),
+    )
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> io::Result<Options> {
+    let mut path = None;
+    let mut breakdown = false;
+    let default_formula = Formula::default();
+    let (mut divisor, mut subtrahend) = (default_formula.divisor(), default_formula.subtrahend());
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--breakdown" => breakdown = true,
+            "--divisor" | "--subtrahend" => {
+                let value = args
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(|| invalid_input(format!("{} expects an integer", arg)))?;
+                if arg == "--divisor" {
+                    divisor = value;
+                } else {
+                    subtrahend = value;
+                }
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(invalid_input(format!("unexpected argument `{}`", arg))),
+        }
+    }
+    let formula = Formula::new(divisor, subtrahend).ok_or_else(|| {
+        invalid_input(format!(
+            "mass / {} - {} does not converge",
+            divisor, subtrahend
+        ))
+    })?;
+    Ok(Options {
+        path,
+        breakdown,
+        formula,
+    })
+}
+
+fn read_input(path: &Option<String>) -> io::Result<String> {
+    match path.as_deref() {
+        None | Some("-") => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            Ok(content)
+        }
+        Some(path) => fs::read_to_string(path),
+    }
+}
 
 fn main() -> io::Result<()> {
-    let file = File::open("./packages/day1/data/input.txt")?;
-    let reader = BufReader::new(file);
-
-    let mut sum = 0;
-    for line in reader.lines() {
-        let mass: i32 = line?.parse().unwrap();
-        let mut fuel = mass;
-        while fuel > 0 {
-            fuel = cmp::max(fuel / 3 - 2, 0);
-            sum += fuel;
+    let options = parse_args(env::args().skip(1))?;
+    let raw = read_input(&options.path)?;
+    let masses = fuel::parse_masses(&raw)?;
+
+    if options.breakdown {
+        println!("{:>10} {:>10} {:>10}", "mass", "fuel", "recursive");
+        for module in fuel::breakdown(&masses, &options.formula) {
+            println!(
+                "{:>10} {:>10} {:>10}",
+                module.mass, module.fuel, module.recursive_fuel
+            );
         }
     }
 
-    println!("{}", sum);
+    println!("{}", fuel::total_fuel(&masses, &options.formula));
+    println!("{}", fuel::total_recursive_fuel(&masses, &options.formula));
 
     Ok(())
 }
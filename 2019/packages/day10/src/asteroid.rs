@@ -0,0 +1,177 @@
+use aoc_core::{ParseError, Source};
+use aoc_grid::Grid;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+pub type Pos = (isize, isize);
+type Direction = (isize, isize);
+
+pub fn parse_map(raw: &str) -> Result<Vec<Pos>, ParseError> {
+    let asteroids: Vec<_> = Grid::try_parse(raw, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?
+    .iter()
+    .filter(|&(_, &asteroid)| asteroid)
+    .map(|((x, y), _)| (x as isize, y as isize))
+    .collect();
+    if asteroids.is_empty() {
+        return Err(Source::new(raw).error(raw, "the map has no asteroid"));
+    }
+    Ok(asteroids)
+}
+
+/// Reduce the offset from `from` to `to` by their gcd, so that all asteroids
+/// lying on the same line of sight from `from` share one direction
+fn direction(from: &Pos, to: &Pos) -> Direction {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let divisor = aoc_math::gcd(dx, dy);
+    (dx / divisor, dy / divisor)
+}
+
+/// Which half of the laser sweep a direction belongs to
+///
+/// The laser starts pointing up and rotates clockwise. Note that y grows downwards,
+/// so the first half covers directions from up (inclusive) to down (exclusive).
+fn half_turn(d: &Direction) -> u8 {
+    if d.0 > 0 || (d.0 == 0 && d.1 < 0) {
+        0
+    } else {
+        1
+    }
+}
+
+/// Compare directions by their clockwise angle from up, without floating point
+fn compare_angle(a: &Direction, b: &Direction) -> Ordering {
+    half_turn(a).cmp(&half_turn(b)).then_with(|| {
+        // within the same half turn, a precedes b when b is clockwise of a
+        let cross = a.0 * b.1 - a.1 * b.0;
+        0.cmp(&cross)
+    })
+}
+
+pub fn count_detectable(asteroids: &[Pos], station: &Pos) -> usize {
+    let mut directions: Vec<Direction> = asteroids
+        .iter()
+        .filter(|&pos| pos != station)
+        .map(|pos| direction(station, pos))
+        .collect();
+    directions.sort_unstable();
+    directions.dedup();
+    directions.len()
+}
+
+/// Find the asteroid which can detect the most other asteroids,
+/// returns the station position and its detectable count
+pub fn find_best_station(asteroids: &[Pos]) -> Option<(Pos, usize)> {
+    asteroids
+        .iter()
+        .map(|station| (*station, count_detectable(asteroids, station)))
+        .max_by_key(|&(_, count)| count)
+}
+
+/// Returns all the other asteroids in the order they are vaporized by the laser at `station`
+pub fn vaporization_order(asteroids: &[Pos], station: &Pos) -> Vec<Pos> {
+    let mut lines_of_sight: HashMap<Direction, Vec<Pos>> = HashMap::new();
+    for pos in asteroids.iter().filter(|&pos| pos != station) {
+        lines_of_sight
+            .entry(direction(station, pos))
+            .or_default()
+            .push(*pos);
+    }
+
+    let distance = |pos: &Pos| (pos.0 - station.0).abs() + (pos.1 - station.1).abs();
+    let mut sweep: Vec<(Direction, Vec<Pos>)> = lines_of_sight.into_iter().collect();
+    sweep.sort_by(|a, b| compare_angle(&a.0, &b.0));
+    for (_, targets) in sweep.iter_mut() {
+        // the nearest target is popped first
+        targets.sort_by_key(|pos| -distance(pos));
+    }
+
+    let mut result = Vec::with_capacity(asteroids.len());
+    while !sweep.is_empty() {
+        for (_, targets) in sweep.iter_mut() {
+            result.push(targets.pop().unwrap());
+        }
+        sweep.retain(|(_, targets)| !targets.is_empty());
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    const SMALL_MAP: &str = ".#..#
+.....
+#####
+....#
+...##";
+
+    const SWEEP_MAP: &str = ".#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....#...###..
+..#.#.....#....##";
+
+    #[test]
+    fn test_best_station() {
+        let asteroids = parse_map(SMALL_MAP).unwrap();
+        assert_eq!(find_best_station(&asteroids), Some(((3, 4), 8)));
+    }
+
+    #[test]
+    fn test_vaporization_order() {
+        let asteroids = parse_map(SWEEP_MAP).unwrap();
+        let order = vaporization_order(&asteroids, &(8, 3));
+        assert_eq!(order.len(), asteroids.len() - 1);
+        assert_eq!(
+            order[0..9],
+            [
+                (8, 1),
+                (9, 0),
+                (9, 1),
+                (10, 0),
+                (9, 2),
+                (11, 1),
+                (12, 1),
+                (11, 2),
+                (15, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_p1() {
+        let asteroids = parse_map(&aoc_input::read(2019, 10, Variant::Input).unwrap()).unwrap();
+        let (station, detectable) = find_best_station(&asteroids).unwrap();
+        assert_eq!(station, (17, 22));
+        assert_eq!(detectable, 288);
+    }
+
+    #[test]
+    fn test_p2() {
+        let asteroids = parse_map(&aoc_input::read(2019, 10, Variant::Input).unwrap()).unwrap();
+        let order = vaporization_order(&asteroids, &(17, 22));
+        assert_eq!(order[199], (6, 16));
+    }
+
+    #[test]
+    fn test_compare_angle() {
+        let clockwise = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+        for pair in clockwise.windows(2) {
+            assert_eq!(compare_angle(&pair[0], &pair[1]), Ordering::Less);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+pub mod asteroid;
+
+use aoc_core::ParseError;
+use asteroid::Pos;
+
+const VAPORIZED_BET: usize = 200;
+
+pub fn process(raw: &str) -> Result<Vec<Pos>, ParseError> {
+    asteroid::parse_map(raw)
+}
+
+pub fn p1(input: &[Pos]) -> usize {
+    asteroid::find_best_station(input).unwrap().1
+}
+
+pub fn p2(input: &[Pos]) -> isize {
+    let (station, _) = asteroid::find_best_station(input).unwrap();
+    let victim = asteroid::vaporization_order(input, &station)[VAPORIZED_BET - 1];
+    victim.0 * 100 + victim.1
+}
+
+aoc_core::solution!(Day10, 2019, 10, Vec<Pos>);
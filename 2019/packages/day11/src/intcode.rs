@@ -1,9 +1,8 @@
+use aoc_core::{ParseError, Source};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, prelude::*, Result};
 use std::ops::{Index, IndexMut};
 
-type Program = Vec<i64>;
+pub type Program = Vec<i64>;
 
 pub struct Memory {
     program: Vec<i64>,
@@ -81,14 +80,25 @@ pub fn interpreter(
             1 | 2 | 7 | 8 => {
                 let input1 = read_value(memory, memory[pc + 1], mode1, rb);
                 let input2 = read_value(memory, memory[pc + 2], mode2, rb);
-                let result: i64;
-                match opcode {
-                    1 => result = input1 + input2,
-                    2 => result = input1 * input2,
-                    7 => result = if input1 < input2 { 1 } else { 0 },
-                    8 => result = if input1 == input2 { 1 } else { 0 },
+                let result = match opcode {
+                    1 => input1 + input2,
+                    2 => input1 * input2,
+                    7 => {
+                        if input1 < input2 {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    8 => {
+                        if input1 == input2 {
+                            1
+                        } else {
+                            0
+                        }
+                    }
                     _ => unreachable!(),
-                }
+                };
                 write_value(memory, memory[pc + 3], mode3, rb, result);
                 pc += 4;
             }
@@ -98,7 +108,7 @@ pub fn interpreter(
                 pc += 2;
             }
             4 | 9 => {
-                let input1 = read_value(&memory, memory[pc + 1], mode1, rb);
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb);
                 match opcode {
                     // stdout
                     4 => {
@@ -119,8 +129,8 @@ pub fn interpreter(
             }
             // jnz, jz
             5 | 6 => {
-                let input1 = read_value(&memory, memory[pc + 1], mode1, rb);
-                let input2 = read_value(&memory, memory[pc + 2], mode2, rb);
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb);
+                let input2 = read_value(memory, memory[pc + 2], mode2, rb);
                 match opcode {
                     5 => {
                         if input1 != 0 {
@@ -149,19 +159,14 @@ pub fn interpreter(
     }
     *entry = pc;
     *rb_entry = rb;
-    return true;
+    true
 }
 
-pub fn read_file_as_program(path: &str) -> io::Result<Program> {
-    let mut file = File::open(path)?;
-    let mut content = String::new();
-    file.read_to_string(&mut content);
-
-    let tape: Vec<i64> = content
+pub fn parse_program(content: &str) -> Result<Program, ParseError> {
+    let source = Source::new(content);
+    content
         .trim_end()
         .split(',')
-        .map(|x| x.to_string().trim_end().parse().unwrap())
-        .collect();
-
-    Ok(tape)
+        .map(|x| source.parse(x.trim_end(), "integer"))
+        .collect()
 }
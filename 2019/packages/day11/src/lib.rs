@@ -1 +1,87 @@
 pub mod intcode;
+
+use aoc_core::ParseError;
+use aoc_grid::{SparseGrid, SparsePos};
+use aoc_image::{Image, BLACK, WHITE};
+
+pub fn process(raw: &str) -> Result<intcode::Program, ParseError> {
+    intcode::parse_program(raw)
+}
+
+/// Paint the hull, starting on a white panel
+fn paint(program: &intcode::Program) -> SparseGrid<char> {
+    let mut memory = intcode::Memory::new(program.clone());
+
+    let mut tiles = SparseGrid::new();
+    let initial_pos = (0, 0);
+    let mut current_tile = initial_pos;
+    tiles.insert(initial_pos, '#');
+    let mut current_direction = 0;
+    let mut pc = 0u64;
+    let mut rb = 0i64;
+
+    loop {
+        let current_tile_color = tiles.entry(current_tile, '.');
+        let mut stdin = Vec::new();
+        stdin.push(if *current_tile_color == '.' { 0 } else { 1 });
+        let mut stdout: Vec<i64> = Vec::new();
+        let halted = intcode::interpreter(&mut memory, &mut stdin, &mut stdout, &mut pc, &mut rb);
+        if !halted {
+            *current_tile_color = if stdout[0] == 0 { '.' } else { '#' };
+            let direction_adjustment = stdout[1] as i32;
+            current_direction = adjust_direction(current_direction, direction_adjustment);
+            current_tile = march_to_next_tile(current_tile, current_direction);
+        } else {
+            assert_eq!(stdout.len(), 0);
+            break;
+        }
+    }
+
+    tiles
+}
+
+pub fn p2(program: &intcode::Program) -> String {
+    let tiles = paint(program);
+    let white = tiles.iter().filter(|&(_, &color)| color == '#');
+    aoc_ocr::read(white.map(|(pos, _)| pos))
+        .unwrap_or_else(|| tiles.render(|tile| tile.copied().unwrap_or(' ')))
+}
+
+fn adjust_direction(current_direction: i32, adjustment: i32) -> i32 {
+    let mut new_direction = current_direction;
+    match adjustment {
+        0 => new_direction -= 1,
+        1 => new_direction += 1,
+        _ => unreachable!(),
+    }
+    if new_direction < 0 {
+        new_direction += 4;
+    } else if new_direction >= 4 {
+        new_direction -= 4;
+    }
+    new_direction
+}
+
+/// Direction 0 is up, y grows downwards as the panels are rendered
+fn march_to_next_tile(current_tile: SparsePos, direction: i32) -> SparsePos {
+    let mut next_tile = current_tile;
+    match direction {
+        0 => next_tile.1 -= 1,
+        1 => next_tile.0 += 1,
+        2 => next_tile.1 += 1,
+        3 => next_tile.0 -= 1,
+        _ => unreachable!(),
+    }
+    next_tile
+}
+
+/// The painted panels, white or black, over the grey unpainted ones
+pub fn render(program: &intcode::Program) -> Image {
+    paint(program).to_image(|tile| match tile {
+        Some('#') => WHITE,
+        Some(_) => BLACK,
+        None => [96, 96, 96],
+    })
+}
+
+aoc_core::solution!(Day11, 2019, 11, intcode::Program, p2, render);
@@ -0,0 +1,69 @@
+pub mod nbody;
+
+use aoc_core::ParseError;
+use aoc_image::{Image, Recorder, Rgb, BLACK};
+
+const ENERGY_STEPS: usize = 1000;
+
+pub fn process(raw: &str) -> Result<nbody::System<3>, ParseError> {
+    Ok(nbody::System::new(nbody::parse_bodies(raw)?))
+}
+
+/// Simulate `steps` steps, `on_step` sees the initial state as step 0, then
+/// the state after each step
+fn simulate(
+    input: &nbody::System<3>,
+    steps: usize,
+    mut on_step: impl FnMut(usize, &nbody::System<3>),
+) -> nbody::System<3> {
+    let mut system = input.clone();
+    on_step(0, &system);
+    for step in 1..=steps {
+        system.step();
+        on_step(step, &system);
+    }
+    system
+}
+
+pub fn p1(input: &nbody::System<3>) -> i64 {
+    simulate(input, ENERGY_STEPS, |_, _| {}).total_energy()
+}
+
+pub fn p2(input: &nbody::System<3>) -> u64 {
+    input.period()
+}
+
+const MOON_COLORS: [Rgb; 4] = [[255, 80, 80], [80, 255, 80], [80, 160, 255], [255, 220, 60]];
+
+/// Record the steps of part 1, images showing the moons seen from above, on
+/// the x and y axes
+pub fn animate(input: &nbody::System<3>, recorder: &mut Recorder) {
+    // frame every image on the whole trajectory
+    let (mut min, mut max) = (input.positions()[0], input.positions()[0]);
+    simulate(input, ENERGY_STEPS, |_, system| {
+        for &position in system.positions() {
+            min = min.min(position);
+            max = max.max(position);
+        }
+    });
+    let (width, height) = (
+        (max[0] - min[0] + 1) as usize,
+        (max[1] - min[1] + 1) as usize,
+    );
+    simulate(input, ENERGY_STEPS, |step, system| {
+        recorder.record(
+            step,
+            || system.to_string(),
+            || {
+                let mut image = Image::new(width, height, BLACK);
+                for (i, position) in system.positions().iter().enumerate() {
+                    let (x, y) = (position[0] - min[0], position[1] - min[1]);
+                    image[(x as usize, y as usize)] = MOON_COLORS[i % MOON_COLORS.len()];
+                }
+                image
+            },
+        )
+    });
+}
+
+aoc_core::solution!(Day12, 2019, 12, nbody::System<3>, animate);
@@ -0,0 +1,243 @@
+use aoc_core::{ParseError, Source};
+use aoc_geom::Point;
+use std::fmt;
+
+/// A group of bodies pulling each other on every axis independently
+///
+/// The number of bodies is taken from the input, the number of dimensions `N`
+/// must match it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct System<const N: usize> {
+    position: Vec<Point<N>>,
+    velocity: Vec<Point<N>>,
+}
+
+/// Parse one body per line in the form of `<x=-1, y=0, z=2>`
+pub fn parse_bodies<const N: usize>(raw: &str) -> Result<Vec<Point<N>>, ParseError> {
+    let source = Source::new(raw);
+    raw.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let inner = source.strip_prefix(line, "<")?;
+            let inner = inner
+                .strip_suffix('>')
+                .ok_or_else(|| source.error_after(inner, "missing `>`"))?;
+            let mut components = inner.splitn(N + 1, ", ");
+            let mut point = Point::ORIGIN;
+            for axis in 0..N {
+                let component = source.field(&mut components, inner, "coordinate")?;
+                let (_, value) = source.split_once(component, "=")?;
+                point[axis] = source.parse(value, "coordinate")?;
+            }
+            match components.next() {
+                Some(extra) => Err(source.error(extra, format!("expected {} coordinates", N))),
+                None => Ok(point),
+            }
+        })
+        .collect()
+}
+
+fn sign(_self: i64, other: i64) -> i64 {
+    if _self == other {
+        0i64
+    } else if other > _self {
+        1i64
+    } else {
+        -1i64
+    }
+}
+
+fn step_axis(position: &mut [i64], velocity: &mut [i64]) {
+    // apply gravity
+    for i in 0..position.len() {
+        for j in 0..position.len() {
+            velocity[i] += sign(position[i], position[j]);
+        }
+    }
+    // apply velocity
+    for (p, v) in position.iter_mut().zip(velocity.iter()) {
+        *p += v;
+    }
+}
+
+impl<const N: usize> System<N> {
+    pub fn new(position: Vec<Point<N>>) -> Self {
+        let velocity = vec![Point::ORIGIN; position.len()];
+        Self { position, velocity }
+    }
+
+    pub fn dimension(&self) -> usize {
+        N
+    }
+
+    pub fn positions(&self) -> &[Point<N>] {
+        &self.position
+    }
+
+    fn axis(&self, axis: usize) -> (Vec<i64>, Vec<i64>) {
+        (
+            self.position.iter().map(|p| p[axis]).collect(),
+            self.velocity.iter().map(|v| v[axis]).collect(),
+        )
+    }
+
+    fn set_axis(&mut self, axis: usize, position: &[i64], velocity: &[i64]) {
+        for (i, (p, v)) in position.iter().zip(velocity).enumerate() {
+            self.position[i][axis] = *p;
+            self.velocity[i][axis] = *v;
+        }
+    }
+
+    pub fn step(&mut self) {
+        for axis in 0..N {
+            let (mut position, mut velocity) = self.axis(axis);
+            step_axis(&mut position, &mut velocity);
+            self.set_axis(axis, &position, &velocity);
+        }
+    }
+
+    /// Sum of each body's potential energy multiplied by its kinetic energy
+    pub fn total_energy(&self) -> i64 {
+        self.position
+            .iter()
+            .zip(self.velocity.iter())
+            .map(|(p, v)| p.manhattan() * v.manhattan())
+            .sum()
+    }
+
+    /// Count the steps until the given axis returns to its current state
+    ///
+    /// Every step is reversible, so the first repeated state is always the current one.
+    pub fn axis_period(&self, axis: usize) -> u64 {
+        let cycle = aoc_cycle::brent(&self.axis(axis), |(position, velocity)| {
+            let (mut position, mut velocity) = (position.clone(), velocity.clone());
+            step_axis(&mut position, &mut velocity);
+            (position, velocity)
+        });
+        debug_assert_eq!(cycle.start, 0);
+        cycle.length as u64
+    }
+
+    /// Count the steps until the whole system returns to its current state
+    pub fn period(&self) -> u64 {
+        (0..N)
+            .map(|axis| self.axis_period(axis))
+            .try_fold(1, aoc_math::lcm)
+            .expect("the period overflows")
+    }
+}
+
+/// One body per line, as the puzzle prints them
+impl<const N: usize> fmt::Display for System<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let write_point = |f: &mut fmt::Formatter, point: &Point<N>| {
+            write!(f, "<")?;
+            for axis in 0..N {
+                let separator = if axis == 0 { "" } else { ", " };
+                match "xyz".chars().nth(axis) {
+                    Some(name) => write!(f, "{}{}={}", separator, name, point[axis])?,
+                    None => write!(f, "{}{}={}", separator, axis, point[axis])?,
+                }
+            }
+            write!(f, ">")
+        };
+        for (p, v) in self.position.iter().zip(self.velocity.iter()) {
+            write!(f, "pos=")?;
+            write_point(f, p)?;
+            write!(f, ", vel=")?;
+            write_point(f, v)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    const EXAMPLE_1: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    const EXAMPLE_2: &str = "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+    fn energy_after(raw: &str, steps: usize) -> i64 {
+        let mut system = System::<3>::new(parse_bodies(raw).unwrap());
+        for _ in 0..steps {
+            system.step();
+        }
+        system.total_energy()
+    }
+
+    #[test]
+    fn test_total_energy() {
+        assert_eq!(energy_after(EXAMPLE_1, 10), 179);
+        assert_eq!(energy_after(EXAMPLE_2, 100), 1940);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut system = System::<3>::new(parse_bodies(EXAMPLE_1).unwrap());
+        system.step();
+        assert_eq!(
+            system.to_string().lines().next(),
+            Some("pos=<x=2, y=-1, z=1>, vel=<x=3, y=-1, z=-1>")
+        );
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(
+            System::<3>::new(parse_bodies(EXAMPLE_1).unwrap()).period(),
+            2772
+        );
+        assert_eq!(
+            System::<3>::new(parse_bodies(EXAMPLE_2).unwrap()).period(),
+            4686774924
+        );
+    }
+
+    #[test]
+    fn test_system_cycle() {
+        let system = System::<3>::new(parse_bodies(EXAMPLE_1).unwrap());
+        let step = |system: &System<3>| {
+            let mut next = system.clone();
+            next.step();
+            next
+        };
+        let cycle = aoc_cycle::hashed(&system, step);
+        assert_eq!((cycle.start, cycle.length as u64), (0, system.period()));
+        let far = cycle.state_at(&system, step, 2772 * 1_000_000 + 10);
+        assert_eq!(far.total_energy(), 179);
+    }
+
+    #[test]
+    fn test_axis_period_counts_each_step_once() {
+        // 0 -> 1 -> 2 -> 2 -> 1 -> 0 -> 0, and the velocities are back to zero
+        let system = System::<1>::new(parse_bodies("<x=0>\n<x=2>").unwrap());
+        assert_eq!(system.dimension(), 1);
+        assert_eq!(system.axis_period(0), 6);
+    }
+
+    #[test]
+    fn test_p1() {
+        assert_eq!(
+            energy_after(&aoc_input::read(2019, 12, Variant::Input).unwrap(), 1000),
+            5937
+        );
+    }
+
+    #[test]
+    fn test_p2() {
+        let system = System::<3>::new(
+            parse_bodies(&aoc_input::read(2019, 12, Variant::Input).unwrap()).unwrap(),
+        );
+        assert_eq!(system.period(), 376203951569712);
+    }
+}
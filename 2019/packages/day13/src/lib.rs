@@ -0,0 +1,304 @@
+use aoc_core::{ParseError, Source};
+use aoc_grid::SparseGrid;
+use aoc_image::{Image, Recorder, Rgb, BLACK, WHITE};
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+pub type Tiles = SparseGrid<i64>;
+
+const SCORE_POS: (i64, i64) = (-1, 0);
+
+// hack: replace the bottom row by walls so that the ball never falls
+const BOTTOM_ROW: &str =
+    "1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,3,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1";
+const WALLED_BOTTOM_ROW: &str =
+    "1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,3,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1";
+
+pub fn process(raw: &str) -> Result<Vec<i64>, ParseError> {
+    let source = Source::new(raw);
+    raw.trim_end()
+        .split(',')
+        .map(|x| source.parse(x.trim_end(), "integer"))
+        .collect()
+}
+
+/// Play the game for free until it halts, returns the final screen
+pub fn play(tape: &[i64]) -> Tiles {
+    play_with(tape, |_, _| {})
+}
+
+/// Apply the `x, y, tile` triples of `output` to the screen
+fn draw(tiles: &mut Tiles, output: &[i64]) {
+    for triple in output.chunks_exact(3) {
+        tiles.insert((triple[0], triple[1]), triple[2]);
+    }
+}
+
+/// Play the game like [`play`], `on_tick` seeing the screen every time the
+/// game reads the joystick
+fn play_with(tape: &[i64], mut on_tick: impl FnMut(usize, &Tiles)) -> Tiles {
+    let mut tape = tape.to_vec();
+    let bottom_row = process(BOTTOM_ROW).unwrap();
+    if let Some(start) = tape
+        .windows(bottom_row.len())
+        .position(|row| row == bottom_row)
+    {
+        tape[start..start + bottom_row.len()].copy_from_slice(&process(WALLED_BOTTOM_ROW).unwrap());
+    }
+
+    let mut memory = Memory::new(tape);
+    let mut stdin = Vec::new();
+    let mut stdout = Vec::new();
+    let mut entry = 0;
+    let mut rb_entry = 0;
+    let mut tiles = SparseGrid::new();
+    let mut drawn = 0;
+    let mut tick = 0;
+    // play for free :D
+    memory[0] = 2;
+    intcode_interpreter(
+        &mut memory,
+        &mut stdin,
+        &mut stdout,
+        &mut entry,
+        &mut rb_entry,
+        &mut |output: &[i64]| {
+            draw(&mut tiles, &output[drawn..]);
+            drawn = output.len();
+            on_tick(tick, &tiles);
+            tick += 1;
+        },
+    );
+    draw(&mut tiles, &stdout[drawn..]);
+
+    tiles
+}
+
+/// The tiles without the score
+fn screen(tiles: &Tiles) -> Tiles {
+    tiles
+        .iter()
+        .filter(|&(pos, _)| pos != SCORE_POS)
+        .map(|(pos, tile)| (pos, *tile))
+        .collect()
+}
+
+/// Draw the score followed by the screen
+pub fn render_tiles(tiles: &Tiles) -> String {
+    let screen = screen(tiles);
+    let score = tiles.get(SCORE_POS).copied().unwrap_or_default();
+    let board = screen.render(|tile| match tile {
+        None | Some(0) => ' ',
+        Some(1) => 'W',
+        Some(2) => 'B',
+        Some(3) => '-',
+        Some(4) => 'O',
+        _ => unreachable!(),
+    });
+    format!("{}\n{}", score, board)
+}
+
+pub fn p2(tape: &[i64]) -> i64 {
+    play(tape)[SCORE_POS]
+}
+
+/// The final screen, without the score
+pub fn render(tape: &[i64]) -> Image {
+    screen(&play(tape)).to_image(tile_color)
+}
+
+/// Record the screen every time the game reads the joystick
+pub fn animate(tape: &[i64], recorder: &mut Recorder) {
+    play_with(tape, |tick, tiles| {
+        recorder.record(
+            tick,
+            || render_tiles(tiles),
+            || screen(tiles).to_image(tile_color),
+        )
+    });
+}
+
+fn tile_color(tile: Option<&i64>) -> Rgb {
+    match tile {
+        None | Some(0) => BLACK,
+        Some(1) => [128, 128, 128],
+        Some(2) => [200, 60, 60],
+        Some(3) => WHITE,
+        Some(4) => [255, 200, 0],
+        _ => unreachable!(),
+    }
+}
+
+struct Memory {
+    program: Vec<i64>,
+    data: HashMap<u64, i64>,
+}
+
+impl Memory {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            program,
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl Index<u64> for Memory {
+    type Output = i64;
+    fn index(&self, index: u64) -> &Self::Output {
+        if index < (self.program.len() as u64) {
+            &self.program[index as usize]
+        } else {
+            match self.data.get(&index) {
+                Some(value) => value,
+                None => &0,
+            }
+        }
+    }
+}
+
+impl IndexMut<u64> for Memory {
+    fn index_mut(&mut self, index: u64) -> &mut Self::Output {
+        if index < (self.program.len() as u64) {
+            &mut self.program[index as usize]
+        } else {
+            self.data.entry(index).or_insert(0)
+        }
+    }
+}
+
+fn parse_op(op: i64) -> [i64; 4] {
+    [op % 100, op % 1000 / 100, op % 10000 / 1000, op / 10000]
+}
+
+fn read_value(memory: &Memory, pos: i64, mode: i64, rb: i64) -> i64 {
+    match mode {
+        0 => memory[pos as u64],
+        1 => pos,
+        2 => memory[(rb + pos) as u64],
+        _ => panic!("ILLEGAL INSTRUCTION FORMAT!"),
+    }
+}
+
+fn write_value(memory: &mut Memory, pos: i64, mode: i64, rb: i64, value: i64) {
+    match mode {
+        0 => memory[pos as u64] = value,
+        2 => memory[(rb + pos) as u64] = value,
+        _ => panic!("ILLEGAL INSTRUCTION FORMAT!"),
+    }
+}
+
+fn intcode_interpreter(
+    memory: &mut Memory,
+    _stdin: &mut Vec<i64>,
+    stdout: &mut Vec<i64>,
+    entry: &mut u64,
+    rb_entry: &mut i64,
+    on_input: &mut impl FnMut(&[i64]),
+) -> bool {
+    let mut pc = *entry;
+    // relative base starts at 0;
+    let mut rb = *rb_entry;
+    let mut time = 0;
+    loop {
+        time += 1;
+        let [opcode, mode1, mode2, mode3] = parse_op(memory[pc]);
+        match opcode {
+            // add, mul
+            1 | 2 | 7 | 8 => {
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb);
+                let input2 = read_value(memory, memory[pc + 2], mode2, rb);
+                let result = match opcode {
+                    1 => input1 + input2,
+                    2 => input1 * input2,
+                    7 => {
+                        if input1 < input2 {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    8 => {
+                        if input1 == input2 {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    _ => unreachable!(),
+                };
+                write_value(memory, memory[pc + 3], mode3, rb, result);
+                pc += 4;
+            }
+            // stdin
+            3 => {
+                on_input(stdout);
+                // leave joystick at 0
+                write_value(memory, memory[pc + 1], mode1, rb, 0);
+                pc += 2;
+            }
+            4 | 9 => {
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb);
+                match opcode {
+                    // stdout
+                    4 => {
+                        stdout.push(input1);
+                    }
+                    // adjust rb
+                    9 => rb += input1,
+                    _ => unreachable!(),
+                }
+                pc += 2;
+            }
+            // jnz, jz
+            5 | 6 => {
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb);
+                let input2 = read_value(memory, memory[pc + 2], mode2, rb);
+                match opcode {
+                    5 => {
+                        if input1 != 0 {
+                            pc = input2 as u64
+                        } else {
+                            pc += 3
+                        }
+                    }
+                    6 => {
+                        if input1 == 0 {
+                            pc = input2 as u64
+                        } else {
+                            pc += 3
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            99 => {
+                break;
+            }
+            _ => {
+                panic!("ILLEGAL OPCODE: {}", opcode);
+            }
+        }
+        // The game does not halt, we manually break when time elapses for a long time
+        if time > 1000000 {
+            break;
+        }
+    }
+    *entry = pc;
+    *rb_entry = rb;
+    true
+}
+
+aoc_core::solution!(Day13, 2019, 13, Vec<i64>, p2, render, animate);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p2() {
+        let input = process(&aoc_input::read(2019, 13, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p2(&input), 18371);
+    }
+}
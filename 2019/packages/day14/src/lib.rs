@@ -0,0 +1,20 @@
+pub mod nanofactory;
+
+use aoc_core::ParseError;
+use nanofactory::Recipes;
+
+const ORE_CAPACITY: usize = 1000000000000;
+
+pub fn process(raw: &str) -> Result<Recipes<'_>, ParseError> {
+    nanofactory::parse_recipes(raw)
+}
+
+pub fn p1(input: &Recipes) -> usize {
+    nanofactory::ore_for_fuel(input, 1)
+}
+
+pub fn p2(input: &Recipes) -> usize {
+    nanofactory::max_fuel(input, ORE_CAPACITY)
+}
+
+aoc_core::solution!(Day14, 2019, 14, Recipes<'a>);
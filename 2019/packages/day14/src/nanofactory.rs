@@ -0,0 +1,363 @@
+use aoc_core::{ParseError, Source};
+use std::collections::{HashMap, HashSet};
+
+pub const ORE: &str = "ORE";
+pub const FUEL: &str = "FUEL";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chemical<'a> {
+    pub name: &'a str,
+    pub count: usize,
+}
+
+#[derive(Debug)]
+pub struct Reaction<'a> {
+    pub output: usize,
+    pub inputs: Vec<Chemical<'a>>,
+}
+
+/// Reactions keyed by their output chemical
+pub type Recipes<'a> = HashMap<&'a str, Reaction<'a>>;
+
+/// Parse `<quantity> <chemical>`, `term` being a slice of `source`
+fn parse_chemical<'a>(source: &Source, term: &'a str) -> Result<Chemical<'a>, ParseError> {
+    let term = term.trim();
+    let malformed = || source.error(term, format!("malformed term `{}`", term));
+    let (quantity, name) = term.split_once(' ').ok_or_else(malformed)?;
+    if name.is_empty() || name.contains(' ') {
+        return Err(malformed());
+    }
+    let count = source.parse(quantity, "quantity")?;
+    if count == 0 {
+        return Err(source.error(quantity, "the quantity must be positive"));
+    }
+    Ok(Chemical { name, count })
+}
+
+/// Parse reactions such as `7 A, 1 E => 1 FUEL`
+///
+/// Every chemical but ore, fuel included, must be produced by a reaction, and
+/// ore by none. Quantities must be positive, and no chemical may be needed to
+/// produce itself.
+pub fn parse_recipes(raw: &str) -> Result<Recipes<'_>, ParseError> {
+    let source = Source::new(raw);
+    let mut recipes = HashMap::new();
+    // chemicals in reading order, checked once every reaction is known
+    let mut produced = vec![];
+    let mut consumed = vec![];
+    for text in raw.lines() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let (lhs, rhs) = text
+            .split_once(" => ")
+            .ok_or_else(|| source.error(text, "expected ` => `"))?;
+        let output = parse_chemical(&source, rhs)?;
+        let inputs = lhs
+            .split(", ")
+            .map(|term| parse_chemical(&source, term))
+            .collect::<Result<Vec<_>, _>>()?;
+        if output.name == ORE {
+            return Err(source.error(output.name, "ORE cannot be produced"));
+        }
+        produced.push(output.name);
+        consumed.extend(inputs.iter().map(|input| input.name));
+        let reaction = Reaction {
+            output: output.count,
+            inputs,
+        };
+        if recipes.insert(output.name, reaction).is_some() {
+            return Err(source.error(
+                output.name,
+                format!("{} is produced by more than one reaction", output.name),
+            ));
+        }
+    }
+    if let Some(name) = consumed
+        .into_iter()
+        .find(|&name| name != ORE && !recipes.contains_key(name))
+    {
+        return Err(source.error(name, format!("no reaction produces {}", name)));
+    }
+    check_acyclic(&source, &recipes, &produced)?;
+    if !recipes.contains_key(FUEL) {
+        return Err(source.error_after(raw.trim_end(), "no reaction produces FUEL"));
+    }
+    Ok(recipes)
+}
+
+/// Reject reactions which need their own output, directly or through other
+/// reactions, visiting the chemicals of `produced` in order
+fn check_acyclic<'a>(
+    source: &Source,
+    recipes: &Recipes<'a>,
+    produced: &[&'a str],
+) -> Result<(), ParseError> {
+    fn visit<'a>(
+        source: &Source,
+        recipes: &Recipes<'a>,
+        name: &'a str,
+        path: &mut HashSet<&'a str>,
+        checked: &mut HashSet<&'a str>,
+    ) -> Result<(), ParseError> {
+        if checked.contains(name) {
+            return Ok(());
+        }
+        path.insert(name);
+        if let Some(reaction) = recipes.get(name) {
+            for input in &reaction.inputs {
+                if path.contains(input.name) {
+                    return Err(source.error(
+                        input.name,
+                        format!("{} is needed to produce itself", input.name),
+                    ));
+                }
+                visit(source, recipes, input.name, path, checked)?;
+            }
+        }
+        path.remove(name);
+        checked.insert(name);
+        Ok(())
+    }
+
+    let mut path = HashSet::new();
+    let mut checked = HashSet::new();
+    produced
+        .iter()
+        .try_for_each(|name| visit(source, recipes, name, &mut path, &mut checked))
+}
+
+/// Order the chemicals so that every chemical precedes all of its ingredients
+///
+/// Only chemicals reachable from `root` are included.
+fn topological_order<'a>(recipes: &Recipes<'a>, root: &'a str) -> Vec<&'a str> {
+    fn visit<'a>(
+        recipes: &Recipes<'a>,
+        name: &'a str,
+        visited: &mut HashSet<&'a str>,
+        postorder: &mut Vec<&'a str>,
+    ) {
+        if !visited.insert(name) {
+            return;
+        }
+        if let Some(reaction) = recipes.get(name) {
+            for input in &reaction.inputs {
+                visit(recipes, input.name, visited, postorder);
+            }
+        }
+        postorder.push(name);
+    }
+
+    let mut visited = HashSet::new();
+    let mut postorder = vec![];
+    visit(recipes, root, &mut visited, &mut postorder);
+    postorder.reverse();
+    postorder
+}
+
+/// Run reactions on demand, keeping the leftover chemicals for later requests
+pub struct Nanofactory<'r, 'a> {
+    recipes: &'r Recipes<'a>,
+    order: Vec<&'a str>,
+    surplus: HashMap<&'a str, usize>,
+    ore_consumed: usize,
+}
+
+impl<'r, 'a> Nanofactory<'r, 'a> {
+    pub fn new(recipes: &'r Recipes<'a>) -> Self {
+        Self {
+            recipes,
+            order: topological_order(recipes, FUEL),
+            surplus: HashMap::new(),
+            ore_consumed: 0,
+        }
+    }
+
+    /// Produce `fuel` FUEL and returns the ORE consumed by this request
+    ///
+    /// Chemicals are resolved in topological order so each one is reacted only once,
+    /// after all of its consumers have booked their demands.
+    pub fn produce(&mut self, fuel: usize) -> usize {
+        let mut demands: HashMap<&str, usize> = HashMap::new();
+        demands.insert(FUEL, fuel);
+        let mut ore = 0;
+        for &name in &self.order {
+            let mut demand = demands.remove(name).unwrap_or(0);
+            if name == ORE {
+                ore += demand;
+                continue;
+            }
+            // `parse_recipes` made sure every other chemical has a reaction
+            let reaction = &self.recipes[name];
+            let stock = self.surplus.entry(name).or_insert(0);
+            let reused = demand.min(*stock);
+            *stock -= reused;
+            demand -= reused;
+
+            let batches = demand.div_ceil(reaction.output);
+            *stock += batches * reaction.output - demand;
+            for input in &reaction.inputs {
+                *demands.entry(input.name).or_insert(0) += batches * input.count;
+            }
+        }
+        self.ore_consumed += ore;
+        ore
+    }
+
+    pub fn surplus(&self, name: &str) -> usize {
+        self.surplus.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn ore_consumed(&self) -> usize {
+        self.ore_consumed
+    }
+}
+
+pub fn ore_for_fuel(recipes: &Recipes, fuel: usize) -> usize {
+    Nanofactory::new(recipes).produce(fuel)
+}
+
+/// Find the maximum FUEL which can be produced with `ore_budget` ORE
+///
+/// Doubles the target until it is out of budget, then binary searches the last gap.
+pub fn max_fuel(recipes: &Recipes, ore_budget: usize) -> usize {
+    let affordable = |fuel: usize| ore_for_fuel(recipes, fuel) <= ore_budget;
+    if !affordable(1) {
+        return 0;
+    }
+    let mut high = 2;
+    while affordable(high) {
+        high *= 2;
+    }
+    // affordable(low) && !affordable(high)
+    let mut low = high / 2;
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if affordable(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    const EXAMPLE_31: &str = "10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL";
+
+    const EXAMPLE_13312: &str = "157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT";
+
+    const ORE_BUDGET: usize = 1000000000000;
+
+    #[test]
+    fn test_ore_for_fuel() {
+        assert_eq!(ore_for_fuel(&parse_recipes(EXAMPLE_31).unwrap(), 1), 31);
+        assert_eq!(
+            ore_for_fuel(&parse_recipes(EXAMPLE_13312).unwrap(), 1),
+            13312
+        );
+    }
+
+    #[test]
+    fn test_max_fuel() {
+        let recipes = parse_recipes(EXAMPLE_13312).unwrap();
+        assert_eq!(max_fuel(&recipes, ORE_BUDGET), 82892753);
+        assert_eq!(max_fuel(&recipes, 13311), 0);
+        assert_eq!(max_fuel(&recipes, 13312), 1);
+    }
+
+    #[test]
+    fn test_surplus_is_reused() {
+        let recipes = parse_recipes(EXAMPLE_31).unwrap();
+        let mut factory = Nanofactory::new(&recipes);
+        // every FUEL needs 28 A, while A is produced in batches of 10
+        for leftover in [2, 4, 6, 8] {
+            assert_eq!(factory.produce(1), 31);
+            assert_eq!(factory.surplus("A"), leftover);
+        }
+        // the 8 leftovers save one batch
+        assert_eq!(factory.produce(1), 21);
+        assert_eq!(factory.surplus("A"), 0);
+        assert_eq!(factory.ore_consumed(), 145);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_recipes("10 ORE => 10 A\n7 A -> 1 FUEL").unwrap_err(),
+            ParseError::new(2, 1, "expected ` => `")
+        );
+        assert_eq!(
+            parse_recipes("10 ORE => 10A").unwrap_err(),
+            ParseError::new(1, 11, "malformed term `10A`")
+        );
+        assert_eq!(
+            parse_recipes("10 ORE => 10 A\nx A => 1 FUEL").unwrap_err(),
+            ParseError::new(2, 1, "invalid quantity `x`")
+        );
+        assert_eq!(
+            parse_recipes("1 ORE => 1 A\n2 ORE => 1 A").unwrap_err(),
+            ParseError::new(2, 12, "A is produced by more than one reaction")
+        );
+        assert_eq!(
+            parse_recipes("1 ORE => 1 A\n2 B => 1 FUEL").unwrap_err(),
+            ParseError::new(2, 3, "no reaction produces B")
+        );
+        // the first unproduced chemical in reading order is reported
+        assert_eq!(
+            parse_recipes("1 C => 1 A\n2 B, 1 A => 1 FUEL").unwrap_err(),
+            ParseError::new(1, 3, "no reaction produces C")
+        );
+        assert_eq!(
+            parse_recipes("1 A => 1 ORE\n1 ORE => 1 FUEL").unwrap_err(),
+            ParseError::new(1, 10, "ORE cannot be produced")
+        );
+        assert_eq!(
+            parse_recipes("10 ORE => 0 FUEL").unwrap_err(),
+            ParseError::new(1, 11, "the quantity must be positive")
+        );
+        assert_eq!(
+            parse_recipes("0 ORE => 1 FUEL").unwrap_err(),
+            ParseError::new(1, 1, "the quantity must be positive")
+        );
+        assert_eq!(
+            parse_recipes("1 B => 1 A\n1 A => 1 B\n1 A => 1 FUEL").unwrap_err(),
+            ParseError::new(2, 3, "A is needed to produce itself")
+        );
+        assert_eq!(
+            parse_recipes("1 ORE, 1 A => 1 A\n1 A => 1 FUEL").unwrap_err(),
+            ParseError::new(1, 10, "A is needed to produce itself")
+        );
+    }
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2019, 14, Variant::Input).unwrap();
+        let recipes = parse_recipes(&raw).unwrap();
+        assert_eq!(ore_for_fuel(&recipes, 1), 1046184);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2019, 14, Variant::Input).unwrap();
+        let recipes = parse_recipes(&raw).unwrap();
+        assert_eq!(max_fuel(&recipes, ORE_BUDGET), 1639374);
+    }
+}
@@ -1,16 +1,14 @@
 use crate::intcode;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 type Metric = i32;
 type Pos = (Metric, Metric);
-type Solution = Vec<Pos>;
 
-#[derive(PartialEq, Clone)]
+#[derive(PartialEq, Clone, Copy)]
 enum TileType {
-    Unknown = 0,
-    Air = 1,
-    Wall = 2,
-    Oxygen = 3,
+    Air,
+    Wall,
+    Oxygen,
 }
 
 type Environment = HashMap<Pos, TileType>;
@@ -20,13 +18,26 @@ const NORTH: i64 = 1;
 const SOUTH: i64 = 2;
 const WEST: i64 = 3;
 const EAST: i64 = 4;
+const DIRECTIONS: [Direction; 4] = [NORTH, SOUTH, WEST, EAST];
+
+/// Puzzle programs map their maze in under 100 thousand instructions
+const MAX_INSTRUCTIONS: usize = 1_000_000;
+/// Puzzle mazes are 41 tiles wide and high, walls included
+const MAX_TILES: usize = 4096;
 
 pub struct Droid {
     memory: intcode::Memory,
     // pc, rb
     program_state: (u64, i64),
+    /// instructions the program may still run
+    budget: usize,
+    environment: Environment,
+}
+
+/// The tiles the droid reached, and where the oxygen system is among them
+pub struct Maze {
     environment: Environment,
-    solution: Solution,
+    oxygen: Pos,
 }
 
 impl Droid {
@@ -36,106 +47,99 @@ impl Droid {
         Droid {
             memory: intcode::Memory::new(program),
             environment,
-            solution: Vec::new(),
             program_state: (0, 0),
+            budget: MAX_INSTRUCTIONS,
         }
     }
 
-    pub fn explore(&mut self) {
-        self.update_environment(NORTH, &(0, 0));
-    }
-
-    pub fn distance(&self) -> usize {
-        unimplemented!()
-    }
-
-    fn reject(&self, solution: &Solution) -> bool {
-        if solution.len() == 1 {
-            return false;
-        }
-
-        let current_pos = solution[solution.len() - 1];
-        // loop
-        if solution[0..solution.len() - 2]
+    /// Walk every reachable tile, coming back to the start, and map them
+    ///
+    /// Fails when the program does not answer a move with one known status
+    /// code, runs too long or maps too many tiles, or when no oxygen system
+    /// was found.
+    pub fn explore(mut self) -> Result<Maze, String> {
+        self.explore_from((0, 0))?;
+        eprintln!(
+            "{} {}",
+            MAX_INSTRUCTIONS - self.budget,
+            self.environment.len()
+        );
+        let oxygen = self
+            .environment
             .iter()
-            .any(|pos| *pos == current_pos)
-        {
-            return true;
-        }
-        if get_type(&current_pos, &self.environment) == TileType::Wall {
-            return true;
-        }
-        false
-    }
-
-    fn accept(&self, solution: &Solution) -> bool {
-        let current_pos = solution[solution.len() - 1];
-        get_type(&current_pos, &self.environment) == TileType::Oxygen
+            .find(|&(_, &tile)| tile == TileType::Oxygen)
+            .map(|(&pos, _)| pos)
+            .ok_or("the droid found no oxygen system")?;
+        Ok(Maze {
+            environment: self.environment,
+            oxygen,
+        })
     }
 
-    fn first(&mut self, solution: Solution) -> Solution {
-        let mut new_solution = solution.clone();
-        let current_pos = solution[solution.len() - 1];
-        let new_pos = self.update_environment(NORTH, &current_pos);
-        new_solution.push(new_pos);
-        new_solution
-    }
-
-    fn next(&mut self, solution: Solution) -> Option<Solution> {
-        assert_ne!(solution.len(), 1);
-        let current_pos = solution[solution.len() - 1];
-        let prev_pos = solution[solution.len() - 2];
-        let next_direction = next_direction(prev_direction(&prev_pos, &current_pos));
-        match next_direction {
-            Some(direction) => {
-                let new_pos = self.update_environment(direction, &prev_pos);
-                let mut new_solution = solution.clone();
-                new_solution[solution.len() - 1] = new_pos;
-                Some(new_solution)
+    /// Depth-first walk from `pos`, where the droid is, stepping back to it
+    /// after each detour
+    fn explore_from(&mut self, pos: Pos) -> Result<(), String> {
+        for &direction in &DIRECTIONS {
+            let new_pos = next_tile(&pos, direction);
+            if self.environment.contains_key(&new_pos) {
+                continue;
             }
-            None => None,
-        }
-    }
-
-    fn backtrack(&mut self, solution: Solution) {
-        if self.reject(&solution) {
-            return;
-        }
-        if self.accept(&solution) {
-            self.solution = solution.clone();
-        }
-        let mut next_solution: Option<Solution> = Some(self.first(solution.to_vec()));
-        loop {
-            match next_solution {
-                Some(solution) => {
-                    self.backtrack(solution.clone());
-                    next_solution = self.next(solution);
-                }
-                None => break,
+            let tile = self.call_sensor(direction)?;
+            self.environment.insert(new_pos, tile);
+            if self.environment.len() > MAX_TILES {
+                return Err(format!("the droid reached more than {} tiles", MAX_TILES));
+            }
+            if tile != TileType::Wall {
+                self.explore_from(new_pos)?;
+                self.call_sensor(opposite(direction))?;
             }
         }
+        Ok(())
     }
 
-    fn update_environment(&mut self, direction: Direction, current_pos: &Pos) -> Pos {
-        let new_pos_type = self.call_sensor(direction);
-        let new_pos = next_tile(current_pos, direction);
-        println!("{}, {}", new_pos.0, new_pos.1);
-        self.environment.entry(new_pos).or_insert(new_pos_type);
-        new_pos
-    }
-
-    fn call_sensor(&mut self, direction: Direction) -> TileType {
+    fn call_sensor(&mut self, direction: Direction) -> Result<TileType, String> {
         let mut stdin = [direction].to_vec();
         let mut stdout = Vec::new();
-        let (mut pc, mut rb) = self.program_state;
-        intcode::interpreter(&mut self.memory, &mut stdin, &mut stdout, &mut pc, &mut rb);
-        assert_eq!(stdout.len(), 1);
-        match stdout[0] {
-            0 => TileType::Wall,
-            1 => TileType::Air,
-            2 => TileType::Oxygen,
-            _ => unreachable!(),
+        let (pc, rb) = &mut self.program_state;
+        intcode::interpreter(
+            &mut self.memory,
+            &mut stdin,
+            &mut stdout,
+            pc,
+            rb,
+            &mut self.budget,
+        )?;
+        match stdout[..] {
+            [0] => Ok(TileType::Wall),
+            [1] => Ok(TileType::Air),
+            [2] => Ok(TileType::Oxygen),
+            [status] => Err(format!("the droid reported an unknown status {}", status)),
+            _ => Err("the droid halted without reporting a status".to_string()),
+        }
+    }
+}
+
+impl Maze {
+    /// The fewest moves from the start to the oxygen system
+    pub fn distance(&self) -> usize {
+        let mut queue = VecDeque::from([((0, 0), 0)]);
+        let mut visited = HashSet::from([(0, 0)]);
+        while let Some((pos, moves)) = queue.pop_front() {
+            if pos == self.oxygen {
+                return moves;
+            }
+            for &direction in &DIRECTIONS {
+                let next = next_tile(&pos, direction);
+                let open = self
+                    .environment
+                    .get(&next)
+                    .is_some_and(|&tile| tile != TileType::Wall);
+                if open && visited.insert(next) {
+                    queue.push_back((next, moves + 1));
+                }
+            }
         }
+        unreachable!("the droid walked from the start to the oxygen system")
     }
 }
 
@@ -149,42 +153,12 @@ fn next_tile(pos: &Pos, direction: Direction) -> Pos {
     }
 }
 
-fn prev_direction(base: &Pos, prev: &Pos) -> Direction {
-    neighbors(base).iter().position(|f| *f == *prev).unwrap() as i64 + 1
-}
-
-fn next_direction(prev_direction: Direction) -> Option<Direction> {
-    match prev_direction {
-        1 | 2 | 3 => Some(prev_direction + 1),
-        4 => None,
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        NORTH => SOUTH,
+        SOUTH => NORTH,
+        EAST => WEST,
+        WEST => EAST,
         _ => unreachable!(),
     }
 }
-
-fn neighbors(pos: &Pos) -> Vec<Pos> {
-    [
-        (pos.0, pos.1 + 1),
-        (pos.0, pos.1 - 1),
-        (pos.0 + 1, pos.1),
-        (pos.0 - 1, pos.1),
-    ]
-    .to_vec()
-}
-
-fn neighbors_except(pos: &Pos, prev: &Pos) -> Vec<Pos> {
-    neighbors(pos)
-        .iter()
-        .filter(|&p| *p != *prev)
-        .map(|x| x.to_owned())
-        .collect()
-}
-
-fn get_type(pos: &Pos, environment: &Environment) -> TileType {
-    environment
-        .get(pos)
-        .map_or(TileType::Unknown, |x| x.clone())
-}
-
-fn is_wall(pos: &Pos, environment: &Environment) -> bool {
-    get_type(pos, environment) == TileType::Wall
-}
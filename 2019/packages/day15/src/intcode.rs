@@ -1,6 +1,6 @@
+use aoc_core::{ParseError, Source};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, prelude::*};
+use std::convert::TryFrom;
 use std::ops::{Index, IndexMut};
 
 pub type Program = Vec<i64>;
@@ -47,94 +47,130 @@ fn parse_op(op: i64) -> [i64; 4] {
     [op % 100, op % 1000 / 100, op % 10000 / 1000, op / 10000]
 }
 
-fn read_value(memory: &Memory, pos: i64, mode: i64, rb: i64) -> i64 {
+fn address(pos: i64) -> Result<u64, String> {
+    u64::try_from(pos).map_err(|_| format!("negative address {}", pos))
+}
+
+fn relative(rb: i64, pos: i64) -> Result<u64, String> {
+    address(rb.checked_add(pos).ok_or("relative address overflow")?)
+}
+
+fn read_value(memory: &Memory, pos: i64, mode: i64, rb: i64) -> Result<i64, String> {
     match mode {
-        0 => memory[pos as u64],
-        1 => pos,
-        2 => memory[(rb + pos) as u64],
-        _ => panic!("ILLEGAL INSTRUCTION FORMAT!"),
+        0 => Ok(memory[address(pos)?]),
+        1 => Ok(pos),
+        2 => Ok(memory[relative(rb, pos)?]),
+        _ => Err(format!("illegal parameter mode {}", mode)),
     }
 }
 
-fn write_value(memory: &mut Memory, pos: i64, mode: i64, rb: i64, value: i64) {
+fn write_value(
+    memory: &mut Memory,
+    pos: i64,
+    mode: i64,
+    rb: i64,
+    value: i64,
+) -> Result<(), String> {
     match mode {
-        0 => memory[pos as u64] = value,
-        2 => memory[(rb + pos) as u64] = value,
-        _ => panic!("ILLEGAL INSTRUCTION FORMAT!"),
+        0 => memory[address(pos)?] = value,
+        2 => memory[relative(rb, pos)?] = value,
+        _ => return Err(format!("illegal parameter mode {}", mode)),
     }
+    Ok(())
 }
 
+/// Run from `entry` until the program halts, returning `true`, or outputs,
+/// returning `false`
+///
+/// Fails on an illegal instruction, an overflow, or once `budget`
+/// instructions have run.
 pub fn interpreter(
     memory: &mut Memory,
     stdin: &mut Vec<i64>,
     stdout: &mut Vec<i64>,
     entry: &mut u64,
     rb_entry: &mut i64,
-) -> bool {
+    budget: &mut usize,
+) -> Result<bool, String> {
     let mut pc = *entry;
     // relative base starts at 0;
     let mut rb = *rb_entry;
     loop {
+        *budget = budget
+            .checked_sub(1)
+            .ok_or("the program runs for too long")?;
         let [opcode, mode1, mode2, mode3] = parse_op(memory[pc]);
         match opcode {
             // add, mul
             1 | 2 | 7 | 8 => {
-                let input1 = read_value(memory, memory[pc + 1], mode1, rb);
-                let input2 = read_value(memory, memory[pc + 2], mode2, rb);
-                let result: i64;
-                match opcode {
-                    1 => result = input1 + input2,
-                    2 => result = input1 * input2,
-                    7 => result = if input1 < input2 { 1 } else { 0 },
-                    8 => result = if input1 == input2 { 1 } else { 0 },
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb)?;
+                let input2 = read_value(memory, memory[pc + 2], mode2, rb)?;
+                let result = match opcode {
+                    1 => input1.checked_add(input2).ok_or("addition overflow")?,
+                    2 => input1
+                        .checked_mul(input2)
+                        .ok_or("multiplication overflow")?,
+                    7 => {
+                        if input1 < input2 {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    8 => {
+                        if input1 == input2 {
+                            1
+                        } else {
+                            0
+                        }
+                    }
                     _ => unreachable!(),
-                }
-                write_value(memory, memory[pc + 3], mode3, rb, result);
+                };
+                write_value(memory, memory[pc + 3], mode3, rb, result)?;
                 pc += 4;
             }
             // stdin
             3 => {
-                println!("pc: {}, rb: {}, std: {}", pc, rb, stdin.len());
-                if stdin.len() > 0 {
-                    write_value(memory, memory[pc + 1], mode1, rb, stdin.pop().unwrap());
+                if !stdin.is_empty() {
+                    write_value(memory, memory[pc + 1], mode1, rb, stdin.pop().unwrap())?;
                 }
                 pc += 2;
             }
             4 | 9 => {
-                let input1 = read_value(&memory, memory[pc + 1], mode1, rb);
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb)?;
                 match opcode {
                     // stdout
                     4 => {
                         stdout.push(input1);
-                        // INTERRUPT when there are 2 output
-                        if stdout.len() == 2 {
+                        // INTERRUPT on every output
+                        if stdout.len() == 1 {
                             pc += 2;
                             *entry = pc;
                             *rb_entry = rb;
-                            return false;
+                            return Ok(false);
                         }
                     }
                     // adjust rb
-                    9 => rb += input1,
+                    9 => rb = rb.checked_add(input1).ok_or("relative base overflow")?,
                     _ => unreachable!(),
                 }
                 pc += 2;
             }
             // jnz, jz
             5 | 6 => {
-                let input1 = read_value(&memory, memory[pc + 1], mode1, rb);
-                let input2 = read_value(&memory, memory[pc + 2], mode2, rb);
+                let input1 = read_value(memory, memory[pc + 1], mode1, rb)?;
+                let input2 = read_value(memory, memory[pc + 2], mode2, rb)?;
                 match opcode {
                     5 => {
                         if input1 != 0 {
-                            pc = input2 as u64
+                            pc = address(input2)?
                         } else {
                             pc += 3
                         }
                     }
                     6 => {
                         if input1 == 0 {
-                            pc = input2 as u64
+                            pc = address(input2)?
                         } else {
                             pc += 3
                         }
@@ -146,25 +182,20 @@ pub fn interpreter(
                 break;
             }
             _ => {
-                panic!("ILLEGAL OPCODE: {}", opcode);
+                return Err(format!("illegal opcode {}", opcode));
             }
         }
     }
     *entry = pc;
     *rb_entry = rb;
-    return true;
+    Ok(true)
 }
 
-pub fn read_file_as_program(path: &str) -> io::Result<Program> {
-    let mut file = File::open(path)?;
-    let mut content = String::new();
-    file.read_to_string(&mut content);
-
-    let tape: Vec<i64> = content
+pub fn parse_program(content: &str) -> Result<Program, ParseError> {
+    let source = Source::new(content);
+    content
         .trim_end()
         .split(',')
-        .map(|x| x.to_string().trim_end().parse().unwrap())
-        .collect();
-
-    Ok(tape)
+        .map(|x| source.parse(x.trim_end(), "integer"))
+        .collect()
 }
@@ -1,2 +1,63 @@
 pub mod droid;
 pub mod intcode;
+
+use aoc_core::{ParseError, Source};
+use droid::{Droid, Maze};
+
+/// Run the repair droid program through the whole maze
+///
+/// A program that misbehaves, or hides no oxygen system, is reported at its
+/// start.
+pub fn process(raw: &str) -> Result<Maze, ParseError> {
+    let program = intcode::parse_program(raw)?;
+    Droid::new(program)
+        .explore()
+        .map_err(|message| Source::new(raw).error(raw, message))
+}
+
+pub fn p1(maze: &Maze) -> usize {
+    maze.distance()
+}
+
+aoc_core::solution!(Day15, 2019, 15, Maze, p1);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let input = process(&aoc_input::read(2019, 15, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p1(&input), 220);
+    }
+
+    #[test]
+    fn test_misbehaving_programs() {
+        let error = |message| Some(ParseError::new(1, 1, message));
+        // halts right away
+        assert_eq!(
+            process("99").err(),
+            error("the droid halted without reporting a status")
+        );
+        // reports a wall whatever the move
+        assert_eq!(
+            process("3,100,104,0,1105,1,0").err(),
+            error("the droid found no oxygen system")
+        );
+        assert_eq!(
+            process("3,100,104,7,1105,1,0").err(),
+            error("the droid reported an unknown status 7")
+        );
+        assert_eq!(
+            process("3,100,104,1,1105,1,0").err(),
+            error("the droid reached more than 4096 tiles")
+        );
+        assert_eq!(
+            process("1105,1,0").err(),
+            error("the program runs for too long")
+        );
+        assert_eq!(process("3,100,37").err(), error("illegal opcode 37"));
+        assert_eq!(process("204,-1").err(), error("negative address -1"));
+    }
+}
@@ -1,14 +1,11 @@
+use aoc_input::Variant;
 use day15::*;
 use std::io::Result;
 
 fn main() -> Result<()> {
-    let program = intcode::read_file_as_program("./packages/day15/data/input.txt")?;
+    let maze = process(&aoc_input::read(2019, 15, Variant::Input)?)?;
 
-    let mut droid = droid::Droid::new(program);
-
-    droid.explore();
-
-    println!("{}", droid.distance());
+    println!("{}", p1(&maze));
 
     Ok(())
 }
@@ -0,0 +1,66 @@
+use aoc_core::{ParseError, Source};
+
+pub fn process(raw: &str) -> Result<Vec<u32>, ParseError> {
+    let source = Source::new(raw);
+    raw.trim_end()
+        .split(',')
+        .map(|x| source.parse(x.trim_end(), "integer"))
+        .collect()
+}
+
+pub fn p2(input: &[u32]) -> u32 {
+    for noun in 0..99 {
+        for verb in 0..99 {
+            if intcode_interpreter(input, noun, verb)[0] == 19690720 {
+                return 100 * noun + verb;
+            }
+        }
+    }
+    unreachable!()
+}
+
+fn intcode_interpreter(vec: &[u32], noun: u32, verb: u32) -> Vec<u32> {
+    let mut values: Vec<u32> = vec.to_vec();
+    /*
+        non-sense
+        Once you have a working computer, the first step is to restore the gravity assist program (your puzzle input) to the "1202 program alarm" state it had just before the last computer caught fire. To do this, before running the program, replace position 1 with the value 12 and replace position 2 with the value 2.
+    */
+    values[1] = noun;
+    values[2] = verb;
+
+    let mut cursor: usize = 0;
+    while values[cursor] != 99 {
+        let input1 = values[cursor + 1] as usize;
+        let input2 = values[cursor + 2] as usize;
+        let output = values[cursor + 3] as usize;
+        match values[cursor] {
+            1 => {
+                values[output] = values[input1] + values[input2];
+                cursor += 4;
+            }
+            2 => {
+                values[output] = values[input1] * values[input2];
+                cursor += 4;
+            }
+            _ => {
+                panic!();
+            }
+        }
+    }
+
+    values
+}
+
+aoc_core::solution!(Day2, 2019, 2, Vec<u32>, p2);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p2() {
+        let input = process(&aoc_input::read(2019, 2, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p2(&input), 9342);
+    }
+}
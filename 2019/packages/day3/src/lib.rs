@@ -0,0 +1,146 @@
+use aoc_core::{ParseError, Source};
+use std::cmp;
+
+pub fn process(raw: &str) -> Result<Vec<Vec<Segment>>, ParseError> {
+    let source = Source::new(raw);
+    let wires = raw
+        .lines()
+        .map(|line| wire_to_segments(&source, line.trim_end()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if wires.len() != 2 {
+        return Err(source.error_after(
+            raw.trim_end(),
+            format!("{} wires instead of 2", wires.len()),
+        ));
+    }
+    Ok(wires)
+}
+
+pub fn p2(input: &[Vec<Segment>]) -> i32 {
+    find_intersection_lowest_delay(&input[0], &input[1])
+}
+
+pub type Segment = [[i32; 2]; 2];
+
+fn is_vertical(seg: &Segment) -> bool {
+    seg[0][0] == seg[1][0]
+}
+
+fn find_intersection_lowest_delay(wire1: &[Segment], wire2: &[Segment]) -> i32 {
+    let mut s1_cost = 0;
+    let mut min_cost = i32::MAX;
+    for s1 in wire1 {
+        let mut s2_cost = 0;
+        if is_vertical(s1) {
+            // vertical
+            for s2 in wire2 {
+                if is_vertical(s2) {
+                    if s1[0][0] != s2[0][0] {
+                    } else {
+                        if cmp::min(s1[0][1], s1[1][1]) > cmp::min(s2[0][1], s2[1][1])
+                            || cmp::max(s1[0][1], s1[1][1]) < cmp::max(s2[0][1], s2[1][1])
+                        {
+                        } else {
+                            panic!("You need to write more")
+                        }
+                    }
+                } else {
+                    if cmp::max(s2[0][0], s2[1][0]) < s1[0][0]
+                        || cmp::min(s2[0][0], s2[1][0]) > s1[0][0]
+                        || cmp::max(s1[0][1], s1[1][1]) < s2[0][1]
+                        || cmp::min(s1[0][1], s1[1][1]) > s2[0][1]
+                    {
+                    } else {
+                        let cost = s1_cost
+                            + s2_cost
+                            + (s1[0][1] - s2[0][1]).abs()
+                            + (s2[0][0] - s1[0][0]).abs();
+                        if cost < min_cost && cost != 0 {
+                            min_cost = cost;
+                        }
+                        break;
+                    }
+                }
+                s2_cost += cmp::max((s2[0][1] - s2[1][1]).abs(), (s2[0][0] - s2[1][0]).abs());
+            }
+        } else {
+            for s2 in wire2 {
+                if !is_vertical(s2) {
+                    if s1[0][1] != s2[0][1] {
+                    } else {
+                        if cmp::min(s1[0][0], s1[1][0]) > cmp::min(s2[0][0], s2[1][0])
+                            || cmp::max(s1[0][0], s1[1][0]) < cmp::max(s2[0][0], s2[1][0])
+                        {
+                        } else {
+                            panic!("You need to write more")
+                        }
+                    }
+                } else {
+                    if cmp::max(s1[0][0], s1[1][0]) < s2[0][0]
+                        || cmp::min(s1[0][0], s1[1][0]) > s2[0][0]
+                        || cmp::max(s2[0][1], s2[1][1]) < s1[0][1]
+                        || cmp::min(s2[0][1], s2[1][1]) > s1[0][1]
+                    {
+                    } else {
+                        let cost = s1_cost
+                            + s2_cost
+                            + (s1[0][1] - s2[0][1]).abs()
+                            + (s2[0][0] - s1[0][0]).abs();
+                        if cost < min_cost && cost != 0 {
+                            min_cost = cost;
+                        }
+                        break;
+                    }
+                }
+                s2_cost += cmp::max((s2[0][1] - s2[1][1]).abs(), (s2[0][0] - s2[1][0]).abs());
+            }
+        }
+        s1_cost += cmp::max((s1[0][1] - s1[1][1]).abs(), (s1[0][0] - s1[1][0]).abs());
+    }
+    min_cost
+}
+
+fn wire_to_segments(source: &Source, wire: &str) -> Result<Vec<Segment>, ParseError> {
+    let mut start: [i32; 2] = [0, 0];
+    let mut result: Vec<[[i32; 2]; 2]> = Vec::new();
+    for seg in wire.split(',') {
+        let split = seg.char_indices().nth(1).map_or(seg.len(), |(i, _)| i);
+        let (direction, length_text) = seg.split_at(split);
+        let length: i32 = source.parse(length_text, "length")?;
+        let mut new_start = start;
+        match direction {
+            "U" => {
+                new_start[1] += length;
+            }
+            "D" => {
+                new_start[1] -= length;
+            }
+            "L" => {
+                new_start[0] -= length;
+            }
+            "R" => {
+                new_start[0] += length;
+            }
+            _ => {
+                return Err(source.error(seg, format!("invalid direction `{}`", direction)));
+            }
+        }
+        result.push([start, new_start]);
+        start = new_start;
+    }
+    Ok(result)
+}
+
+aoc_core::solution!(Day3, 2019, 3, Vec<Vec<Segment>>, p2);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p2() {
+        let input = process(&aoc_input::read(2019, 3, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p2(&input), 27890);
+    }
+}
@@ -0,0 +1,45 @@
+use aoc_core::{ParseError, Source};
+use std::ops::RangeInclusive;
+
+pub fn process(raw: &str) -> Result<RangeInclusive<u32>, ParseError> {
+    let source = Source::new(raw);
+    let (start, end) = source.split_once(raw.trim(), "-")?;
+    Ok(source.parse(start, "password")?..=source.parse(end, "password")?)
+}
+
+pub fn p2(input: &RangeInclusive<u32>) -> usize {
+    input.clone().filter(|&password| is_valid(password)).count()
+}
+
+fn is_valid(password: u32) -> bool {
+    let mut digit_pairs: Vec<[char; 2]> = Vec::new();
+    let mut i = 0;
+
+    let digits: String = password.to_string();
+
+    while i + 1 < digits.len() {
+        let mut dp = digits[i..i + 2].chars();
+        digit_pairs.push([dp.next().unwrap(), dp.next().unwrap()]);
+        i += 1;
+    }
+
+    let equal_pairs: Vec<&[char; 2]> = digit_pairs.iter().filter(|p| p[0] == p[1]).collect();
+    digit_pairs.iter().all(|p| p[0] <= p[1])
+        && equal_pairs
+            .iter()
+            .any(|p| equal_pairs.iter().filter(|q| p[0] == q[0]).count() == 1)
+}
+
+aoc_core::solution!(Day4, 2019, 4, RangeInclusive<u32>, p2);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p2() {
+        let input = process(&aoc_input::read(2019, 4, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p2(&input), 1131);
+    }
+}
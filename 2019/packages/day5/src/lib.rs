@@ -0,0 +1,113 @@
+use aoc_core::{ParseError, Source};
+
+pub fn process(raw: &str) -> Result<Vec<i32>, ParseError> {
+    let source = Source::new(raw);
+    raw.trim_end()
+        .split(',')
+        .map(|x| source.parse(x.trim_end(), "integer"))
+        .collect()
+}
+
+pub fn p2(input: &[i32]) -> i32 {
+    let mut stdin = [5].to_vec();
+    let mut stdout = Vec::new();
+    intcode_interpreter(input, &mut stdin, &mut stdout)
+}
+
+fn parse_opcode(opcode: i32) -> [i32; 4] {
+    [
+        opcode % 100,
+        opcode % 1000 / 100,
+        opcode % 10000 / 1000,
+        opcode / 10000,
+    ]
+}
+
+fn resolve_value(memory: &[i32], value: i32, mode: i32) -> i32 {
+    match mode {
+        0 => memory[value as usize],
+        1 => value,
+        _ => panic!("ILLEGAL INSTRUCTION FORMAT!"),
+    }
+}
+
+fn intcode_interpreter(mem: &[i32], stdin: &mut Vec<i32>, stdout: &mut Vec<i32>) -> i32 {
+    let mut memory = mem.to_vec();
+
+    let mut pc: usize = 0;
+    loop {
+        let parsed = parse_opcode(memory[pc]);
+        match parsed[0] {
+            // add, mul
+            1 | 2 | 7 | 8 => {
+                let input1 = resolve_value(&memory, memory[pc + 1], parsed[1]);
+                let input2 = resolve_value(&memory, memory[pc + 2], parsed[2]);
+                let operand = memory[pc + 3] as usize;
+                match parsed[0] {
+                    1 => memory[operand] = input1 + input2,
+                    2 => memory[operand] = input1 * input2,
+                    7 => memory[operand] = if input1 < input2 { 1 } else { 0 },
+                    8 => memory[operand] = if input1 == input2 { 1 } else { 0 },
+                    _ => unreachable!(),
+                }
+                pc += 4;
+            }
+            // stdin
+            3 => {
+                let operand = memory[pc + 1] as usize;
+                memory[operand] = stdin.pop().unwrap();
+                pc += 2;
+            }
+            // stdout
+            4 => {
+                let operand = memory[pc + 1] as usize;
+                stdout.push(memory[operand]);
+                pc += 2;
+            }
+            // jnz, jz
+            5 | 6 => {
+                let input1 = resolve_value(&memory, memory[pc + 1], parsed[1]);
+                let input2 = resolve_value(&memory, memory[pc + 2], parsed[2]);
+                match parsed[0] {
+                    5 => {
+                        if input1 != 0 {
+                            pc = input2 as usize
+                        } else {
+                            pc += 3
+                        }
+                    }
+                    6 => {
+                        if input1 == 0 {
+                            pc = input2 as usize
+                        } else {
+                            pc += 3
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            99 => {
+                break;
+            }
+            _ => {
+                panic!("ILLEGAL OPCODE!");
+            }
+        }
+    }
+
+    *stdout.last().unwrap()
+}
+
+aoc_core::solution!(Day5, 2019, 5, Vec<i32>, p2);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p2() {
+        let input = process(&aoc_input::read(2019, 5, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p2(&input), 2140710);
+    }
+}
@@ -0,0 +1,64 @@
+use aoc_core::{ParseError, Source};
+use std::cmp;
+use std::collections::HashMap;
+
+pub fn process(raw: &str) -> Result<HashMap<String, String>, ParseError> {
+    let source = Source::new(raw);
+    let mut map = HashMap::new();
+    for line in raw.lines() {
+        let (orbitee, orbitor) = source.split_once(line, ")")?;
+        if map
+            .insert(orbitor.to_string(), orbitee.to_string())
+            .is_some()
+        {
+            return Err(source.error(orbitor, format!("`{}` orbits two objects", orbitor)));
+        }
+    }
+    Ok(map)
+}
+
+pub fn p2(map: &HashMap<String, String>) -> usize {
+    let mut you_ancestors = find_ancestors(map, &"YOU".to_string());
+    you_ancestors.reverse();
+    let mut san_ancestors = find_ancestors(map, &"SAN".to_string());
+    san_ancestors.reverse();
+
+    you_ancestors.len() + san_ancestors.len()
+        - 2 * find_common_starts(&you_ancestors, &san_ancestors)
+}
+
+fn find_ancestors(map: &HashMap<String, String>, node: &String) -> Vec<String> {
+    let mut p = map.get(node);
+    let mut result = Vec::new();
+    while let Some(parent) = p {
+        result.push(parent.to_string());
+        p = map.get(parent);
+    }
+    result
+}
+
+fn find_common_starts(ancestor1: &[String], ancestor2: &[String]) -> usize {
+    let mut r = 0;
+    for i in 0..cmp::min(ancestor1.len(), ancestor2.len()) {
+        if ancestor1[i] == ancestor2[i] {
+            r += 1;
+        } else {
+            break;
+        }
+    }
+    r
+}
+
+aoc_core::solution!(Day6, 2019, 6, HashMap<String, String>, p2);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p2() {
+        let input = process(&aoc_input::read(2019, 6, Variant::Input).unwrap()).unwrap();
+        assert_eq!(p2(&input), 439);
+    }
+}
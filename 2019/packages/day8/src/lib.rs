@@ -0,0 +1,75 @@
+use aoc_core::{ParseError, Source};
+use aoc_grid::Grid;
+use aoc_image::{Image, BLACK, WHITE};
+
+const LAYER_WIDTH: usize = 25;
+const LAYER_HEIGHT: usize = 6;
+const LAYER_AREA: usize = LAYER_HEIGHT * LAYER_WIDTH;
+
+pub fn process(raw: &str) -> Result<Vec<char>, ParseError> {
+    let source = Source::new(raw);
+    let data = raw.trim_end();
+    if let Some(i) = data.find(|c| !matches!(c, '0'..='2')) {
+        return Err(source.unexpected(&data[i..]));
+    }
+    if data.is_empty() || !data.len().is_multiple_of(LAYER_AREA) {
+        return Err(source.error_after(
+            data,
+            format!("{} pixels do not make layers of {}", data.len(), LAYER_AREA),
+        ));
+    }
+    Ok(data.chars().collect())
+}
+
+/// Stack the layers, the first one on top
+fn decode(data: &[char]) -> Vec<char> {
+    let data_len = data.len();
+    let mut cursor = data_len - LAYER_AREA;
+
+    let mut base_layer: Vec<char> = data[cursor..data_len].to_vec();
+
+    while cursor > 0 {
+        let next_cursor = cursor - LAYER_AREA;
+        let filter: Vec<char> = data[next_cursor..cursor].to_vec();
+        apply_layer(&mut base_layer, &filter);
+        cursor = next_cursor;
+    }
+    base_layer
+}
+
+pub fn p2(data: &[char]) -> String {
+    let base_layer = decode(data);
+    let image = Grid::from_vec(LAYER_WIDTH, base_layer.iter().map(|&c| c == '1').collect());
+    aoc_ocr::read_grid(&image).unwrap_or_else(|| render_layer(&base_layer))
+}
+fn render_layer(layer: &[char]) -> String {
+    let mut result = String::new();
+    for i in 0..LAYER_HEIGHT {
+        for j in 0..LAYER_WIDTH {
+            result.push(match layer[i * LAYER_WIDTH + j] {
+                '2' => ' ',
+                '1' => '■',
+                '0' => ' ',
+                _ => unreachable!(),
+            });
+        }
+        result.push('\n');
+    }
+    result
+}
+
+fn apply_layer(base: &mut [char], filter: &[char]) {
+    for i in 0..LAYER_AREA {
+        if filter[i] != '2' {
+            base[i] = filter[i];
+        }
+    }
+}
+
+/// The decoded image, white pixels on black
+pub fn render(data: &[char]) -> Image {
+    let layer = decode(data);
+    Grid::from_vec(LAYER_WIDTH, layer).to_image(|&c| if c == '1' { WHITE } else { BLACK })
+}
+
+aoc_core::solution!(Day8, 2019, 8, Vec<char>, p2, render);
@@ -0,0 +1,11 @@
+use a2020::day3::{p1, p2, process};
+use aoc_input::Variant;
+use std::io;
+
+fn main() -> io::Result<()> {
+    let raw = aoc_input::read(2020, 3, Variant::Input)?;
+    let input = process(&raw)?;
+    println!("Answer of p1: {}", p1(&input));
+    println!("Answer of p2: {}", p2(&input));
+    Ok(())
+}
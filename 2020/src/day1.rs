@@ -1,50 +1,84 @@
+use aoc_core::{ParseError, Source};
 use std::collections::HashSet;
-use std::fs;
-use std::io;
-
-fn main() -> io::Result<()> {
-    let raw = fs::read_to_string(fs::canonicalize("./data/day1/input.txt")?)?;
-    let input = process(&raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-    Ok(())
-}
 
-fn process(raw: &str) -> Vec<i32> {
+/// Expenses, two and three of which sum to 2020
+pub fn process(raw: &str) -> Result<Vec<i32>, ParseError> {
+    let source = Source::new(raw);
     let mut result: Vec<i32> = vec![];
     for n in raw.split('\n') {
         if n.is_empty() {
             continue;
         }
-        let int: i32 = n.parse().unwrap();
+        let int: i32 = source.parse(n, "expense")?;
+        // bigger expenses cannot be part of a sum, and would overflow it
+        if !(0..=2020).contains(&int) {
+            return Err(source.error(n, format!("the expense {} is not within 0..=2020", n)));
+        }
         result.push(int);
     }
-    result
+    if pair_product(&result).is_none() {
+        return Err(source.error(raw, "no two expenses sum to 2020"));
+    }
+    if triple_product(&result).is_none() {
+        return Err(source.error(raw, "no three expenses sum to 2020"));
+    }
+    Ok(result)
 }
 
-fn p1(input: &Vec<i32>) -> i32 {
+fn pair_product(input: &[i32]) -> Option<i32> {
     let mut diff_set = HashSet::new();
     for n in input {
         let diff = 2020 - *n;
         if diff_set.contains(n) {
-            return diff * *n;
+            return Some(diff * *n);
         }
         diff_set.insert(diff);
     }
-    unreachable!()
+    None
 }
 
-fn p2(input: &Vec<i32>) -> i32 {
+fn triple_product(input: &[i32]) -> Option<i32> {
     let mut diff_set = HashSet::new();
     for n in input {
         let diff = 2020 - *n;
         for m in input {
             let two_sum = *m + *n;
             if diff_set.contains(&two_sum) {
-                return (2020 - two_sum) * *m * *n;
+                return Some((2020 - two_sum) * *m * *n);
             }
         }
         diff_set.insert(diff);
     }
-    unreachable!()
+    None
+}
+
+pub fn p1(input: &[i32]) -> i32 {
+    // `process` made sure there is one
+    pair_product(input).unwrap()
+}
+
+pub fn p2(input: &[i32]) -> i32 {
+    triple_product(input).unwrap()
+}
+
+aoc_core::solution!(Day1, 2020, 1, Vec<i32>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2020, 1, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 1010299);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2020, 1, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 42140160);
+    }
 }
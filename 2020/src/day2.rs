@@ -1,77 +1,90 @@
-use std::fs;
-use std::io;
-use std::num::ParseIntError;
-use std::str::FromStr;
+use aoc_core::{ParseError, Source};
 
 #[derive(Debug)]
-struct Policy {
+pub struct Policy {
     min: usize,
     max: usize,
     matcher: char,
 }
 
-impl FromStr for Policy {
-    type Err = ParseIntError;
-
-    // 1-6 x
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut space_iter = s.rsplit(" ");
-        let matcher = space_iter.next().unwrap().chars().next().unwrap();
-        let mut hyphen_iter = space_iter.next().unwrap().split("-");
-        let min = hyphen_iter.next().unwrap().parse::<usize>()?;
-        let max = hyphen_iter.next().unwrap().parse::<usize>()?;
-        Ok(Policy {
-            min: min,
-            max: max,
-            matcher: matcher,
-        })
-    }
+// 1-6 x
+fn parse_policy(source: &Source, s: &str) -> Result<Policy, ParseError> {
+    let (range, matcher_text) = source.split_once(s, " ")?;
+    let mut matcher_chars = matcher_text.chars();
+    let matcher = match (matcher_chars.next(), matcher_chars.next()) {
+        (Some(matcher), None) => matcher,
+        _ => return Err(source.error(matcher_text, format!("invalid letter `{}`", matcher_text))),
+    };
+    let (min_text, max_text) = source.split_once(range, "-")?;
+    let min = source.parse::<usize>(min_text, "number")?;
+    let max = source.parse::<usize>(max_text, "number")?;
+    Ok(Policy { min, max, matcher })
 }
 
-fn main() -> io::Result<()> {
-    let raw = fs::read_to_string(fs::canonicalize("./data/day2.txt")?)?;
-    let input = process(&raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-    Ok(())
-}
-
-fn process(raw: &str) -> Vec<(Policy, &str)> {
+pub fn process(raw: &str) -> Result<Vec<(Policy, &str)>, ParseError> {
+    let source = Source::new(raw);
     let mut result: Vec<(Policy, &str)> = vec![];
     for line in raw.split('\n') {
         if line.is_empty() {
             continue;
         }
-        let mut split_iter = line.split(": ");
-        let policy: Policy = split_iter.next().unwrap().parse().unwrap();
-        let str = split_iter.next().unwrap();
-        result.push((policy, &str));
+        let (policy_text, str) = source.split_once(line, ": ")?;
+        let policy = parse_policy(&source, policy_text)?;
+        // both policies read positions 1 <= min < max in the password
+        if policy.min == 0 || policy.min >= policy.max || policy.max > str.chars().count() {
+            return Err(source.error(
+                policy_text,
+                format!("`{}` does not fit the password", policy_text),
+            ));
+        }
+        result.push((policy, str));
     }
-    result
+    Ok(result)
 }
 
-fn p1(input: &Vec<(Policy, &str)>) -> usize {
+pub fn p1(input: &[(Policy, &str)]) -> usize {
     input
-        .into_iter()
+        .iter()
         .filter(|(policy, pw)| {
             let matched = pw.matches(policy.matcher).count();
-            return matched >= policy.min && matched <= policy.max;
+            matched >= policy.min && matched <= policy.max
         })
         .count()
 }
 
-fn p2(input: &Vec<(Policy, &str)>) -> usize {
+pub fn p2(input: &[(Policy, &str)]) -> usize {
     input
-        .into_iter()
+        .iter()
         .filter(|(policy, pw)| {
             let matcher = policy.matcher;
             let mut chars = pw
                 .chars()
                 .skip(policy.min - 1)
                 .step_by(policy.max - policy.min);
-            return ((chars.next().unwrap() == matcher) as i32
-                ^ (chars.next().unwrap() == matcher) as i32)
-                != 0;
+            ((chars.next().unwrap() == matcher) as i32 ^ (chars.next().unwrap() == matcher) as i32)
+                != 0
         })
         .count()
 }
+
+aoc_core::solution!(Day2, 2020, 2, Vec<(Policy, &'a str)>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2020, 2, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 538);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2020, 2, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 489);
+    }
+}
@@ -1,54 +1,60 @@
-use std::fs;
-use std::io;
-
-fn main() -> io::Result<()> {
-    let input = process();
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-    Ok(())
-}
+use aoc_core::ParseError;
+use aoc_grid::Grid;
 
-fn process() -> Vec<Vec<u8>> {
-    let raw = fs::read_to_string(fs::canonicalize("./data/day3.txt").unwrap()).unwrap();
-    let mut result: Vec<Vec<u8>> = vec![];
-    for line in raw.split('\n') {
-        if line.is_empty() {
-            continue;
-        }
-        let vector: Vec<u8> = line
-            .chars()
-            .map(|c| match c {
-                '#' => 1,
-                '.' => 0,
-                _ => unreachable!(),
-            })
-            .collect();
-        result.push(vector);
-    }
-    result
+/// Trees of the map, which repeats itself to the right
+pub fn process(raw: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::try_parse(raw, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
-fn traverse_trees(input: &Vec<Vec<u8>>, right: usize, down: usize) -> usize {
+/// Count the trees encountered on the slope `right`, `down` from the top-left
+pub fn traverse_trees(input: &Grid<bool>, right: usize, down: usize) -> usize {
     let mut result: usize = 0;
-    let width = input[0].len();
-    let mut j = right;
-    for i in (down..input.len()).step_by(down) {
-        j = j % width;
-        if input[i][j] == 1 {
+    let mut pos = (0, 0);
+    for _ in (down..input.height()).step_by(down) {
+        let Some(next) = input.wrapping_offset(pos, (right as isize, down as isize)) else {
+            break;
+        };
+        pos = next;
+        if input[pos] {
             result += 1;
         }
-        j += right;
     }
     result
 }
 
-fn p1(input: &Vec<Vec<u8>>) -> usize {
+pub fn p1(input: &Grid<bool>) -> usize {
     traverse_trees(input, 3, 1)
 }
 
-fn p2(input: &Vec<Vec<u8>>) -> usize {
+pub fn p2(input: &Grid<bool>) -> usize {
     let data = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
     data.iter()
         .map(|(right, down)| traverse_trees(input, *right, *down))
-        .fold(1, |acc, x| acc * x)
+        .product()
+}
+
+aoc_core::solution!(Day3, 2020, 3, Grid<bool>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2020, 3, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 276);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2020, 3, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 7812180000);
+    }
 }
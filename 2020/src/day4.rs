@@ -1,28 +1,23 @@
+use aoc_core::{ParseError, Source};
 use regex::Regex;
-use std::fs;
-use std::io;
 use std::str::FromStr;
 
-fn main() -> io::Result<()> {
-    let raw = fs::read_to_string(fs::canonicalize("./data/day4.txt").unwrap()).unwrap();
-    let input = process(&raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-    Ok(())
-}
-
-fn process(raw: &str) -> Vec<&str> {
+pub fn process(raw: &str) -> Result<Vec<&str>, ParseError> {
+    let source = Source::new(raw);
     let mut result: Vec<&str> = vec![];
     for line in raw.split("\n\n") {
         if line.is_empty() {
             continue;
         }
+        for item in line.split_whitespace() {
+            source.split_once(item, ":")?;
+        }
         result.push(line);
     }
-    result
+    Ok(result)
 }
 
-fn p1(input: &Vec<&str>) -> usize {
+pub fn p1(input: &[&str]) -> usize {
     input
         .iter()
         .filter(|line| {
@@ -43,7 +38,7 @@ fn p1(input: &Vec<&str>) -> usize {
                     _ => unimplemented!(),
                 }
             }
-            return valid_flag == 0x7f;
+            valid_flag == 0x7f
         })
         .count()
 }
@@ -101,7 +96,7 @@ pid (Passport ID) - a nine-digit number, including leading zeroes.
 cid (Country ID) - ignored, missing or not.
 
 */
-fn p2(input: &Vec<&str>) -> usize {
+pub fn p2(input: &[&str]) -> usize {
     let hcl_reg = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
     let ecl_reg = Regex::new(r"^(?:amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
     let pid_reg = Regex::new(r"^[0-9]{9}$").unwrap();
@@ -117,7 +112,7 @@ fn p2(input: &Vec<&str>) -> usize {
                 match key {
                     "byr" => match value.parse::<usize>() {
                         Ok(v) => {
-                            if v >= 1920 && v <= 2002 {
+                            if (1920..=2002).contains(&v) {
                                 valid_flag |= 0x1
                             }
                         }
@@ -125,7 +120,7 @@ fn p2(input: &Vec<&str>) -> usize {
                     },
                     "iyr" => match value.parse::<usize>() {
                         Ok(v) => {
-                            if v >= 2010 && v <= 2020 {
+                            if (2010..=2020).contains(&v) {
                                 valid_flag |= 0x2
                             }
                         }
@@ -133,7 +128,7 @@ fn p2(input: &Vec<&str>) -> usize {
                     },
                     "eyr" => match value.parse::<usize>() {
                         Ok(v) => {
-                            if v >= 2020 && v <= 2030 {
+                            if (2020..=2030).contains(&v) {
                                 valid_flag |= 0x4
                             }
                         }
@@ -144,12 +139,12 @@ fn p2(input: &Vec<&str>) -> usize {
                             let value = v.value;
                             match v.unit {
                                 HeightUnit::Cm => {
-                                    if value >= 150 && value <= 193 {
+                                    if (150..=193).contains(&value) {
                                         valid_flag |= 0x8;
                                     }
                                 }
                                 HeightUnit::Inch => {
-                                    if value >= 59 && value <= 76 {
+                                    if (59..=76).contains(&value) {
                                         valid_flag |= 0x8;
                                     }
                                 }
@@ -176,7 +171,29 @@ fn p2(input: &Vec<&str>) -> usize {
                     _ => unimplemented!(),
                 }
             }
-            return valid_flag == 0x7f;
+            valid_flag == 0x7f
         })
         .count()
 }
+
+aoc_core::solution!(Day4, 2020, 4, Vec<&'a str>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2020, 4, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 206);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2020, 4, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 123);
+    }
+}
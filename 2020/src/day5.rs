@@ -1,61 +1,69 @@
-use std::fs;
-use std::io;
-
-fn main() -> io::Result<()> {
-    let raw = fs::read_to_string(fs::canonicalize("./data/day5.txt")?)?;
-    let input = process(&raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-    Ok(())
-}
+use aoc_core::{ParseError, Source};
 
-fn process(raw: &str) -> Vec<usize> {
+pub fn process(raw: &str) -> Result<Vec<usize>, ParseError> {
+    let source = Source::new(raw);
     let mut result: Vec<_> = vec![];
     for line in raw.split('\n') {
         if line.is_empty() {
             continue;
         }
-        let len = line.len();
-        let row = usize::from_str_radix(
-            &line[0..len - 3]
-                .chars()
-                .map(|x| match x {
-                    'B' => '1',
-                    'F' => '0',
-                    _ => unreachable!(),
-                })
-                .collect::<String>(),
-            2,
-        )
-        .unwrap();
-        let column = usize::from_str_radix(
-            &line[len - 3..len]
-                .chars()
-                .map(|x| match x {
-                    'R' => '1',
-                    'L' => '0',
-                    _ => unreachable!(),
-                })
-                .collect::<String>(),
-            2,
-        )
-        .unwrap();
-        result.push(row * 8 + column);
+        if line.len() != 10 {
+            return Err(source.error(line, format!("`{}` is not a seat", line)));
+        }
+        // FBFBBFFRLR => 0101100101, row then column
+        let mut seat = 0;
+        for (i, x) in line.char_indices() {
+            let bit = match (i, x) {
+                (0..=6, 'B') | (7.., 'R') => 1,
+                (0..=6, 'F') | (7.., 'L') => 0,
+                _ => return Err(source.unexpected(&line[i..])),
+            };
+            seat = seat << 1 | bit;
+        }
+        result.push(seat);
     }
     result.sort();
-    result
+    if missing_seat(&result).is_none() {
+        return Err(source.error(raw, "no seat is missing between two taken ones"));
+    }
+    Ok(result)
 }
 
-fn p1(input: &Vec<usize>) -> usize {
+/// The free seat between two taken ones of `input`, which is sorted
+fn missing_seat(input: &[usize]) -> Option<usize> {
+    input
+        .windows(2)
+        .find(|pair| pair[1] == pair[0] + 2)
+        .map(|pair| pair[0] + 1)
+}
+
+pub fn p1(input: &[usize]) -> usize {
+    // `process` made sure there are at least two seats
     input[input.len() - 1]
 }
 
-fn p2(input: &Vec<usize>) -> usize {
-    for i in 0..input.len() - 1 {
-        let seat = input[i];
-        if input[i + 1] == seat + 2 {
-            return seat + 1;
-        }
+pub fn p2(input: &[usize]) -> usize {
+    missing_seat(input).unwrap()
+}
+
+aoc_core::solution!(Day5, 2020, 5, Vec<usize>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2020, 5, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 871);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2020, 5, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 640);
     }
-    unreachable!();
 }
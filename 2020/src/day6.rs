@@ -1,15 +1,7 @@
-use std::fs;
-use std::io;
+use aoc_core::{ParseError, Source};
 
-fn main() -> io::Result<()> {
-    let raw = fs::read_to_string(fs::canonicalize("./data/day6.txt")?)?;
-    let input = process(&raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-    Ok(())
-}
-
-fn process(raw: &str) -> Vec<Vec<u32>> {
+pub fn process(raw: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let source = Source::new(raw);
     let mut result: Vec<_> = vec![];
     for line in raw.split("\n\n") {
         if line.is_empty() {
@@ -20,29 +12,53 @@ fn process(raw: &str) -> Vec<Vec<u32>> {
             line.split('\n')
                 .filter(|x| !x.is_empty())
                 .map(|x| {
-                    x.chars()
-                        .fold(0, |acc, c| acc | (1 << ((c as u32 - 'a' as u32) + 1)))
+                    x.char_indices().try_fold(0, |acc, (i, c)| match c {
+                        'a'..='z' => Ok(acc | (1 << ((c as u32 - 'a' as u32) + 1))),
+                        _ => Err(source.unexpected(&x[i..])),
+                    })
                 })
-                .collect::<Vec<u32>>(),
+                .collect::<Result<Vec<u32>, _>>()?,
         );
     }
-    result
+    Ok(result)
 }
 
-fn p1(input: &Vec<Vec<u32>>) -> u32 {
+pub fn p1(input: &[Vec<u32>]) -> u32 {
     input
         .iter()
         // Concat each answer with bitwise OR
         // abc(0x111) | ad(0x1001) => abcd (0x1111)
         .map(|card| card.iter().fold(0, |acc, x| acc | x).count_ones())
-        .fold(0, |acc, x| acc + x)
+        .sum()
 }
 
-fn p2(input: &Vec<Vec<u32>>) -> u32 {
+pub fn p2(input: &[Vec<u32>]) -> u32 {
     input
         .iter()
         // Concat each answer with bitwise AND
         // abc(0x111) & ad(0x1001) => a (0x1)
         .map(|card| card.iter().fold(!0, |acc, x| acc & x).count_ones())
-        .fold(0, |acc, x| acc + x)
+        .sum()
+}
+
+aoc_core::solution!(Day6, 2020, 6, Vec<Vec<u32>>);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2020, 6, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 6259);
+    }
+
+    #[test]
+    fn test_p2() {
+        let raw = aoc_input::read(2020, 6, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 3178);
+    }
 }
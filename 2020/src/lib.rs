@@ -0,0 +1,18 @@
+use aoc_core::Registry;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+/// Register the solution of every day
+pub fn register(registry: &mut Registry) {
+    registry.register::<day1::Day1>();
+    registry.register::<day2::Day2>();
+    registry.register::<day3::Day3>();
+    registry.register::<day4::Day4>();
+    registry.register::<day5::Day5>();
+    registry.register::<day6::Day6>();
+}
@@ -0,0 +1,23 @@
+use a2020::day3;
+
+const SLOPE_EXAMPLE: &str = "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+#[test]
+fn test_traverse_trees() {
+    let input = day3::process(SLOPE_EXAMPLE).unwrap();
+    assert_eq!(day3::traverse_trees(&input, 3, 1), 7);
+    assert_eq!(day3::traverse_trees(&input, 1, 2), 2);
+    assert_eq!(day3::p2(&input), 336);
+}
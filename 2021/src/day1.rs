@@ -1,49 +1,40 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day1.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
-fn process(raw: &str) -> Vec<u16> {
-    raw.lines().map(|n| n.parse().unwrap()).collect()
+pub fn process(raw: &str) -> Result<Vec<u16>, ParseError> {
+    let source = Source::new(raw);
+    raw.lines().map(|n| source.parse(n, "depth")).collect()
 }
 
-fn p1(input: &[u16]) -> usize {
+pub fn p1(input: &[u16]) -> usize {
     (1..input.len())
-        .into_iter()
         .filter(|&i| input[i] > input[i - 1])
         .count()
 }
 
-fn p2(input: &[u16]) -> usize {
+pub fn p2(input: &[u16]) -> usize {
     (3..input.len())
-        .into_iter()
         .filter(|&i| input[i] > input[i - 3])
         .count()
 }
 
+aoc_core::solution!(Day1, 2021, 1, Vec<u16>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 1, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 1162);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 1, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 1190);
     }
 }
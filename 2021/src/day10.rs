@@ -1,19 +1,8 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day10.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
 #[repr(u8)]
 #[derive(Copy, Clone)]
-enum Token {
+pub enum Token {
     ParenR,
     BracketR,
     CurlyBracketR,
@@ -32,29 +21,68 @@ fn token_match_openning_element(token: &Token, openning: &Token) -> bool {
     *openning as u8 - *token as u8 == 4
 }
 
-type Input<'a> = Vec<Token>;
+pub type Input<'a> = Vec<Token>;
 
-fn process(raw: &str) -> Vec<Input> {
-    raw.lines()
+/// The most chunks an incomplete line may leave open, for its score to fit
+/// in a `usize`
+const MAX_OPEN: usize = 27;
+
+pub fn process(raw: &str) -> Result<Vec<Input<'_>>, ParseError> {
+    let source = Source::new(raw);
+    let mut incomplete = 0;
+    let result: Vec<_> = raw
+        .lines()
         .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '(' => Token::ParenL,
-                    ')' => Token::ParenR,
-                    '[' => Token::BracketL,
-                    ']' => Token::BracketR,
-                    '{' => Token::CurlyBracketL,
-                    '}' => Token::CurlyBracketR,
-                    '<' => Token::Lt,
-                    '>' => Token::Gt,
-                    _ => unreachable!(),
+            let tokens: Vec<_> = line
+                .char_indices()
+                .map(|(i, c)| match c {
+                    '(' => Ok(Token::ParenL),
+                    ')' => Ok(Token::ParenR),
+                    '[' => Ok(Token::BracketL),
+                    ']' => Ok(Token::BracketR),
+                    '{' => Ok(Token::CurlyBracketL),
+                    '}' => Ok(Token::CurlyBracketR),
+                    '<' => Ok(Token::Lt),
+                    '>' => Ok(Token::Gt),
+                    _ => Err(source.unexpected(&line[i..])),
                 })
-                .collect()
+                .collect::<Result<_, _>>()?;
+            let mut openning_stack = vec![];
+            for ((i, _), token) in line.char_indices().zip(&tokens) {
+                if is_openning(token) {
+                    openning_stack.push(token);
+                } else if let Some(openning) = openning_stack.pop() {
+                    if !token_match_openning_element(token, openning) {
+                        // corrupted
+                        return Ok(tokens);
+                    }
+                } else {
+                    return Err(source.error(&line[i..], "no chunk is open"));
+                }
+            }
+            if openning_stack.len() > MAX_OPEN {
+                return Err(source.error(
+                    line,
+                    format!(
+                        "{} chunks are left open, at most {}",
+                        openning_stack.len(),
+                        MAX_OPEN
+                    ),
+                ));
+            }
+            if !openning_stack.is_empty() {
+                incomplete += 1;
+            }
+            Ok(tokens)
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+    if incomplete == 0 {
+        return Err(source.error(raw, "no line is incomplete"));
+    }
+    Ok(result)
 }
 
-fn p1(input: &[Input]) -> usize {
+pub fn p1(input: &[Input]) -> usize {
     let closing_token_score: Vec<usize> = vec![3, 57, 1197, 25137];
     let mut sum: usize = 0;
     for tokens in input {
@@ -73,7 +101,7 @@ fn p1(input: &[Input]) -> usize {
     sum
 }
 
-fn p2(input: &[Input]) -> usize {
+pub fn p2(input: &[Input]) -> usize {
     let closing_token_point: Vec<usize> = vec![1, 2, 3, 4];
     let mut scores = vec![];
     'outer: for tokens in input {
@@ -105,21 +133,24 @@ fn p2(input: &[Input]) -> usize {
     }
 }
 
+aoc_core::solution!(Day10, 2021, 10, Vec<Input<'a>>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 10, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 216297);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 10, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 2165057169);
     }
 }
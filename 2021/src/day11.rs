@@ -1,65 +1,39 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day11.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    println!("Answer of p1: {}", p1(raw));
-    println!("Answer of p2: {}", p2(raw));
-}
+use aoc_core::ParseError;
+use aoc_grid::Grid;
+use aoc_image::{Recorder, WHITE};
 
 const FLASH_THRESHOLD: u8 = 10;
 
-type Input = Vec<Vec<u8>>;
+pub type Input = Grid<u8>;
 
-fn process(raw: &str) -> Input {
-    raw.lines()
-        .map(|line| line.chars().map(|c| c as u8 - b'0').collect())
-        .collect()
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    Grid::try_parse(raw, |c| c.to_digit(10).map(|d| d as u8))
 }
 
 /// Simulate the octupus flashing, returns a sum of flash counts
-fn simulate(input: &mut [Vec<u8>], width: &usize, height: &usize) -> usize {
-    let get_neighbors = |y: usize, x: usize| {
-        vec![
-            (y.overflowing_sub(1).0, x),
-            (y.overflowing_sub(1).0, x + 1),
-            (y, x + 1),
-            (y + 1, x + 1),
-            (y + 1, x),
-            (y + 1, x.overflowing_sub(1).0),
-            (y, x.overflowing_sub(1).0),
-            (y.overflowing_sub(1).0, x.overflowing_sub(1).0),
-        ]
-        .into_iter()
-        .filter(|(y, x)| y < height && x < width)
-    };
-
+fn simulate(input: &mut Input) -> usize {
     let mut flash_stack = vec![];
     // first pass: increment energy level by one
     // and build initial flash stack
-    for (y, line) in input.iter_mut().enumerate() {
-        for (x, level) in line.iter_mut().enumerate() {
-            *level = (*level + 1) % FLASH_THRESHOLD;
-            if *level == 0 {
-                flash_stack.push((y, x));
-            }
+    for (pos, level) in input.iter_mut() {
+        *level = (*level + 1) % FLASH_THRESHOLD;
+        if *level == 0 {
+            flash_stack.push(pos);
         }
     }
 
     let mut flashed_count = 0;
     // second pass: consume flash stack and regiser new
     // flashing octopus
-    while let Some((y, x)) = flash_stack.pop() {
+    while let Some(pos) = flash_stack.pop() {
         flashed_count += 1;
-        for (y, x) in get_neighbors(y, x) {
-            let level = &mut input[y][x];
+        let neighbors: Vec<_> = input.neighbors8(pos).collect();
+        for neighbor in neighbors {
+            let level = &mut input[neighbor];
             if *level > 0 {
                 *level = (*level + 1) % FLASH_THRESHOLD;
                 if *level == 0 {
-                    flash_stack.push((y, x));
+                    flash_stack.push(neighbor);
                 }
             }
         }
@@ -67,47 +41,88 @@ fn simulate(input: &mut [Vec<u8>], width: &usize, height: &usize) -> usize {
     flashed_count
 }
 
-fn p1(raw: &str) -> usize {
-    let mut input = process(raw);
-    let width = input[0].len();
-    let height = input.len();
-
+pub fn p1(input: &Input) -> usize {
+    let mut input = input.clone();
     let mut flashed_count = 0;
     for _ in 0..100 {
-        flashed_count += simulate(&mut input, &width, &height);
+        flashed_count += simulate(&mut input);
     }
     flashed_count
 }
 
-fn p2(raw: &str) -> usize {
-    let mut input = process(raw);
-    let width = input[0].len();
-    let height = input.len();
-    let total = width * height;
+/// Simulate until every octopus flashes at once, returns the number of steps
+///
+/// `on_step` sees the initial state as step 0, then the state after each step.
+fn synchronize(input: &Input, mut on_step: impl FnMut(usize, &Input)) -> usize {
+    let mut input = input.clone();
+    let total = input.width() * input.height();
 
     let mut step = 0;
+    on_step(step, &input);
     loop {
         step += 1;
-        if simulate(&mut input, &width, &height) == total {
+        let flashed_count = simulate(&mut input);
+        on_step(step, &input);
+        if flashed_count == total {
             break;
         }
     }
     step
 }
 
+pub fn p2(input: &Input) -> usize {
+    synchronize(input, |_, _| {})
+}
+
+/// Record the octopuses until they synchronize, flashing ones in white and
+/// the others darker the less energy they have
+pub fn animate(input: &Input, recorder: &mut Recorder) {
+    synchronize(input, |step, octopuses| {
+        recorder.record(
+            step,
+            || octopuses.render(|&level| char::from(b'0' + level)),
+            || {
+                octopuses.to_image(|&level| match level {
+                    0 => WHITE,
+                    _ => [0, 0, level * 20],
+                })
+            },
+        )
+    });
+}
+
+aoc_core::solution!(Day11, 2021, 11, Input, animate);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        assert_eq!(p1(raw), 1667);
+        let raw = aoc_input::read(2021, 11, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 1667);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        assert_eq!(p2(raw), 488);
+        let raw = aoc_input::read(2021, 11, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 488);
+    }
+
+    #[test]
+    fn test_animate() {
+        let raw = aoc_input::read(2021, 11, Variant::Example).unwrap();
+        let input = process(&raw).unwrap();
+        let dir = std::env::temp_dir().join(format!("a2021-day11-{}", std::process::id()));
+        let mut recorder = Recorder::new(&dir, 5).unwrap();
+        animate(&input, &mut recorder);
+        // steps 0, 5, …, 195
+        assert_eq!(recorder.finish().unwrap(), 40);
+        let synchronized = std::fs::read_to_string(dir.join("000195.txt")).unwrap();
+        assert!(synchronized.lines().all(|line| line == "0000000000"));
+        std::fs::remove_dir_all(dir).unwrap();
     }
 }
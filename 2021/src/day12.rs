@@ -1,18 +1,6 @@
+use aoc_core::{ParseError, Source};
 use std::collections::{HashMap, HashSet};
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day12.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
 /// Map cave label to u8
 const START_CAVE_LABEL: u8 = 0;
 const END_CAVE_LABEL: u8 = 1;
@@ -25,33 +13,57 @@ const END_CAVE_LABEL: u8 = 1;
 ///   0: HashMap { 1: 2 }
 ///   1: HashMap { 0: 2 }
 /// }
-type Input = HashMap<u8, HashMap<u8, usize>>;
+pub type Input = HashMap<u8, HashMap<u8, usize>>;
 
-fn process(raw: &str) -> Input {
+fn is_big(label: &str) -> bool {
+    label.as_bytes()[0] <= b'Z'
+}
+
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
     let mut labels = HashMap::from([("start", START_CAVE_LABEL), ("end", END_CAVE_LABEL)]);
     let mut raw_map = HashMap::new();
     let mut big_cave_ids = HashSet::new();
+    // `None` once the labels run out
     let mut register_index = |a| {
-        let index = labels.len() as u8;
-        *labels.entry(a).or_insert_with(|| {
-            if a.as_bytes()[0] <= b'Z' {
-                big_cave_ids.insert(index);
-            }
-            index
-        })
+        if let Some(&index) = labels.get(a) {
+            return Some(index);
+        }
+        let index = u8::try_from(labels.len()).ok()?;
+        if is_big(a) {
+            big_cave_ids.insert(index);
+        }
+        labels.insert(a, index);
+        Some(index)
     };
     let mut register_edge =
         |from_id, to_id| raw_map.entry(from_id).or_insert_with(Vec::new).push(to_id);
 
     for line in raw.lines() {
-        let (from, to) = line.split_once('-').unwrap();
-        let (from_id, to_id) = (register_index(from), register_index(to));
+        let (from, to) = source.split_once(line, "-")?;
+        for label in [from, to] {
+            if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(source.error(label, format!("invalid cave `{}`", label)));
+            }
+        }
+        // the paths through both would go round in circles
+        if is_big(from) && is_big(to) {
+            return Err(source.error(line, "two big caves are connected"));
+        }
+        let (Some(from_id), Some(to_id)) = (register_index(from), register_index(to)) else {
+            return Err(source.error(line, "more than 256 caves"));
+        };
         register_edge(from_id, to_id);
         register_edge(to_id, from_id);
     }
+    for (label, id) in [("start", START_CAVE_LABEL), ("end", END_CAVE_LABEL)] {
+        if !raw_map.contains_key(&id) {
+            return Err(source.error(raw, format!("no passage leads to `{}`", label)));
+        }
+    }
 
     // forward big cave's children (must be small caves) to its parent (must be small caves)
-    raw_map
+    Ok(raw_map
         .iter()
         .filter_map(|(key, children)| {
             if big_cave_ids.contains(key) {
@@ -72,7 +84,7 @@ fn process(raw: &str) -> Input {
             }
             Some((*key, children_count_map))
         })
-        .collect()
+        .collect())
 }
 
 struct P1State {
@@ -84,7 +96,7 @@ struct P1State {
     sum: usize,
 }
 
-fn p1(input: &Input) -> usize {
+pub fn p1(input: &Input) -> usize {
     let initial_state = P1State {
         current_cave: END_CAVE_LABEL,
         distance: 0,
@@ -132,7 +144,7 @@ struct P2State {
     visited_cave_twice: bool,
 }
 
-fn p2(input: &Input) -> usize {
+pub fn p2(input: &Input) -> usize {
     let initial_state = P2State {
         current_cave: END_CAVE_LABEL,
         distance: 0,
@@ -180,21 +192,24 @@ fn p2(input: &Input) -> usize {
     count
 }
 
+aoc_core::solution!(Day12, 2021, 12, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 12, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 3856);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 12, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 116692);
     }
 }
@@ -1,19 +1,8 @@
+use aoc_core::{ParseError, Source};
+use aoc_image::{Image, BLACK, WHITE};
 use std::cmp::max;
 use std::collections::HashSet;
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day13.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
 type Location = (usize, usize);
 
 #[derive(Debug)]
@@ -24,29 +13,51 @@ struct Folding {
 
 type Dots = HashSet<Location>;
 
-struct Input {
+pub struct Input {
     dots: Dots,
     foldings: Vec<Folding>,
 }
 
-fn process(raw: &str) -> Input {
-    let (dots_text, foldings_text) = raw.split_once("\n\n").unwrap();
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let (dots_text, foldings_text) = source.split_once(raw, "\n\n")?;
     let dots = dots_text
         .lines()
         .map(|line| {
-            let (x, y) = line.split_once(',').unwrap();
-            (x.parse().unwrap(), y.parse().unwrap())
+            let (x, y) = source.split_once(line, ",")?;
+            Ok((source.parse(x, "x")?, source.parse(y, "y")?))
         })
-        .collect();
+        .collect::<Result<Dots, ParseError>>()?;
+    let mut folded = dots.clone();
     let foldings = foldings_text
         .lines()
         .map(|line| {
-            let horizontal = &line[11..12] == "y";
-            let axis: usize = line[13..].parse().unwrap();
-            Folding { horizontal, axis }
+            let instruction = source.strip_prefix(line, "fold along ")?;
+            let (direction, axis) = source.split_once(instruction, "=")?;
+            let horizontal = match direction {
+                "x" => false,
+                "y" => true,
+                _ => return Err(source.error(direction, format!("invalid axis `{}`", direction))),
+            };
+            let axis = source.parse(axis, "position")?;
+            let folding = Folding { horizontal, axis };
+            // the dots folded so far must all land on the paper
+            let extent = folded
+                .iter()
+                .map(|&(x, y)| if horizontal { y } else { x })
+                .max()
+                .unwrap_or(0);
+            if extent.saturating_sub(axis) > axis {
+                return Err(source.error(line, "the fold leaves dots off the paper"));
+            }
+            fold(&mut folded, &folding);
+            Ok(folding)
         })
-        .collect();
-    Input { dots, foldings }
+        .collect::<Result<Vec<_>, _>>()?;
+    if foldings.is_empty() {
+        return Err(source.error(foldings_text, "no fold instruction"));
+    }
+    Ok(Input { dots, foldings })
 }
 
 fn fold(dots: &mut Dots, folding: &Folding) {
@@ -64,14 +75,14 @@ fn fold(dots: &mut Dots, folding: &Folding) {
     for dot in to_be_removed {
         dots.remove(&dot);
         if folding.horizontal {
-            dots.insert((dot.0, axis * 2 - dot.1));
+            dots.insert((dot.0, axis - (dot.1 - axis)));
         } else {
-            dots.insert((axis * 2 - dot.0, dot.1));
+            dots.insert((axis - (dot.0 - axis), dot.1));
         }
     }
 }
 
-fn p1(input: &Input) -> usize {
+pub fn p1(input: &Input) -> usize {
     let mut merged_dots = input.dots.clone();
     fold(&mut merged_dots, &input.foldings[0]);
     merged_dots.len()
@@ -94,38 +105,76 @@ fn print_dots(dots: &Dots) -> String {
     result
 }
 
-fn p2(input: &Input) -> String {
+fn fold_all(input: &Input) -> Dots {
     let mut merged_dots = input.dots.clone();
     for folding in &input.foldings {
         fold(&mut merged_dots, folding);
     }
-    print_dots(&merged_dots)
+    merged_dots
+}
+
+pub fn p2(input: &Input) -> String {
+    let merged_dots = fold_all(input);
+    let dots = merged_dots.iter().map(|&(x, y)| (x as i64, y as i64));
+    aoc_ocr::read(dots).unwrap_or_else(|| print_dots(&merged_dots))
 }
 
+/// The dots left once the paper is folded, white on black
+pub fn render(input: &Input) -> Image {
+    let dots = fold_all(input);
+    let width = dots.iter().map(|dot| dot.0 + 1).max().unwrap_or_default();
+    let height = dots.iter().map(|dot| dot.1 + 1).max().unwrap_or_default();
+    Image::from_fn(width, height, |x, y| {
+        if dots.contains(&(x, y)) {
+            WHITE
+        } else {
+            BLACK
+        }
+    })
+}
+
+aoc_core::solution!(Day13, 2021, 13, Input, render);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 13, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 653);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 13, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), "LKREBPRK");
+    }
+
+    #[test]
+    fn test_p2_unreadable() {
+        // the example folds into a square, which is drawn instead
+        let raw = aoc_input::read(2021, 13, Variant::Example).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(
             p2(&input),
             "
-#    #  # ###  #### ###  ###  ###  #  #
-#    # #  #  # #    #  # #  # #  # # # 
-#    ##   #  # ###  ###  #  # #  # ##  
-#    # #  ###  #    #  # ###  ###  # # 
-#    # #  # #  #    #  # #    # #  # # 
-#### #  # #  # #### ###  #    #  # #  #"
+#####
+#   #
+#   #
+#   #
+#####"
         );
     }
+
+    #[test]
+    fn test_render() {
+        let raw = aoc_input::read(2021, 13, Variant::Example).unwrap();
+        let image = render(&process(&raw).unwrap());
+        assert_eq!((image.width(), image.height()), (5, 5));
+        assert_eq!((image[(0, 0)], image[(2, 2)]), (WHITE, BLACK));
+    }
 }
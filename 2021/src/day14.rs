@@ -1,24 +1,12 @@
+use aoc_core::{ParseError, Source};
 use std::cmp::{max, min};
 use std::collections::HashMap;
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day14.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
 /// A pair is any adjacent pair, every char, except the end and start,
 /// in polymer template belongs to exactly two pairs.
 type Pair = (char, char);
 
-struct Input {
+pub struct Input {
     /// A virtural pair (end, start) used to counter the fact that
     /// the start and end can only belongs to one pair.
     /// In other words, if we concat the end pair with pairs, then every
@@ -29,9 +17,30 @@ struct Input {
     rules: HashMap<Pair, (Pair, Pair)>,
 }
 
-fn process(raw: &str) -> Input {
-    let (node_text, rule_text) = raw.split_once("\n\n").unwrap();
+/// The characters of `text`, which must have `count` of them
+fn elements(source: &Source, text: &str, count: usize) -> Result<Vec<char>, ParseError> {
+    let chars: Vec<_> = text.chars().collect();
+    if chars.len() != count {
+        return Err(source.error(
+            text,
+            format!(
+                "`{}` has {} elements instead of {}",
+                text,
+                chars.len(),
+                count
+            ),
+        ));
+    }
+    Ok(chars)
+}
+
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let (node_text, rule_text) = source.split_once(raw, "\n\n")?;
     let chars: Vec<_> = node_text.chars().collect();
+    if chars.len() < 2 {
+        return Err(source.error(node_text, "the template needs two elements"));
+    }
     let mut pairs = HashMap::<Pair, usize>::new();
     for i in 0..chars.len() - 1 {
         let count = pairs.entry((chars[i], chars[i + 1])).or_insert(0);
@@ -42,13 +51,15 @@ fn process(raw: &str) -> Input {
     let rules = rule_text
         .lines()
         .map(|line| {
-            let chars: Vec<_> = line.chars().collect();
-            let key = (chars[0], chars[1]);
-            let value = ((chars[0], chars[6]), (chars[6], chars[1]));
-            (key, value)
+            let (pair_text, insert_text) = source.split_once(line, " -> ")?;
+            let pair = elements(&source, pair_text, 2)?;
+            let insert = elements(&source, insert_text, 1)?[0];
+            let key = (pair[0], pair[1]);
+            let value = ((pair[0], insert), (insert, pair[1]));
+            Ok((key, value))
         })
-        .collect();
-    Input { end, pairs, rules }
+        .collect::<Result<_, _>>()?;
+    Ok(Input { end, pairs, rules })
 }
 
 fn simulate(
@@ -66,9 +77,9 @@ fn simulate(
             pairs.remove(from);
         }
     }
-    // inherit remaning pairs
+    // inherit remaning pairs, which rules may have produced too
     for (pair, count) in pairs {
-        new_pairs.insert(*pair, *count);
+        *new_pairs.entry(*pair).or_insert(0) += *count;
     }
     new_pairs
 }
@@ -106,29 +117,98 @@ fn stat(input: &Input, step: usize) -> usize {
     max_count - min_count
 }
 
-fn p1(input: &Input) -> usize {
+pub fn p1(input: &Input) -> usize {
     stat(input, 10)
 }
 
-fn p2(input: &Input) -> usize {
+pub fn p2(input: &Input) -> usize {
     stat(input, 40)
 }
 
+aoc_core::solution!(Day14, 2021, 14, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
+    use aoc_rand::Rng;
+
+    /// Insert the elements into the template itself
+    fn naive(template: &str, rules: &HashMap<(char, char), char>, step: usize) -> usize {
+        let mut polymer: Vec<char> = template.chars().collect();
+        for _ in 0..step {
+            let mut next = vec![polymer[0]];
+            for pair in polymer.windows(2) {
+                if let Some(&insert) = rules.get(&(pair[0], pair[1])) {
+                    next.push(insert);
+                }
+                next.push(pair[1]);
+            }
+            polymer = next;
+        }
+        let mut counts = HashMap::new();
+        for element in polymer {
+            *counts.entry(element).or_insert(0) += 1;
+        }
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 14, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 2194);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 14, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 2360298895777);
     }
+
+    #[test]
+    fn test_pair_without_rule_also_produced() {
+        // ABB becomes ABBB: BB is left as is and produced by AB
+        let input = process("ABB\n\nAB -> B").unwrap();
+        assert_eq!(stat(&input, 1), 2);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        const ELEMENTS: [char; 4] = ['B', 'C', 'H', 'N'];
+        let mut rng = Rng::new(14);
+        for _ in 0..200 {
+            let template: String = (0..2 + rng.below(6))
+                .map(|_| *rng.pick(&ELEMENTS))
+                .collect();
+            // some pairs have no rule and stay as they are
+            let mut rules = HashMap::new();
+            for left in ELEMENTS {
+                for right in ELEMENTS {
+                    if rng.chance(3, 4) {
+                        rules.insert((left, right), *rng.pick(&ELEMENTS));
+                    }
+                }
+            }
+            let raw = format!(
+                "{}\n\n{}",
+                template,
+                rules
+                    .iter()
+                    .map(|((left, right), insert)| format!("{}{} -> {}", left, right, insert))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+            let input = process(&raw).unwrap();
+            let step = rng.below(9);
+            assert_eq!(
+                stat(&input, step),
+                naive(&template, &rules, step),
+                "{} steps of\n{}",
+                step,
+                raw
+            );
+        }
+    }
 }
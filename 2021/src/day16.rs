@@ -1,15 +1,7 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day16.txt"
-    };
-}
+use aoc_core::{ParseError, Source};
 
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+/// Deeper packets are rejected rather than overflowing the stack
+const MAX_DEPTH: usize = 100;
 
 #[derive(Debug)]
 enum PacketContent {
@@ -23,89 +15,134 @@ enum PacketContent {
 }
 
 #[derive(Debug)]
-struct Packet {
+pub struct Packet {
     version: u16,
     content: PacketContent,
 }
 
-type Input = Packet;
+pub type Input = Packet;
 
-fn parse_packet(input: &str, pos: &mut usize) -> Packet {
-    let advance_by = |pos: &mut usize, size: usize| -> &str {
-        let start = *pos;
-        *pos += size;
-        &input[start..*pos]
-    };
+/// The bits of a hexadecimal transmission, read from the most significant one
+struct Bits<'a> {
+    source: Source<'a>,
+    hex: &'a str,
+    bits: String,
+    pos: usize,
+}
+
+impl Bits<'_> {
+    /// An error on the hexadecimal digit holding the current bit
+    fn error(&self, message: &str) -> ParseError {
+        let index = (self.pos / 4).min(self.hex.len());
+        self.source.error(&self.hex[index..], message)
+    }
+
+    fn advance_by(&mut self, size: usize) -> Result<&str, ParseError> {
+        if self.pos + size > self.bits.len() {
+            return Err(self.error("the packet is truncated"));
+        }
+        let start = self.pos;
+        self.pos += size;
+        Ok(&self.bits[start..self.pos])
+    }
 
-    let parse_fixed_length =
-        |pos: &mut usize, size| -> u16 { u16::from_str_radix(advance_by(pos, size), 2).unwrap() };
+    fn parse_fixed_length(&mut self, size: usize) -> Result<u16, ParseError> {
+        Ok(u16::from_str_radix(self.advance_by(size)?, 2).unwrap())
+    }
 
-    let parse_variable_length = |pos: &mut usize| -> usize {
-        let mut result = 0;
+    fn parse_variable_length(&mut self) -> Result<usize, ParseError> {
+        let mut result: usize = 0;
         loop {
-            let should_break = advance_by(pos, 1);
-            result = result << 4 | parse_fixed_length(pos, 4) as usize;
-            if should_break == "0" {
+            let should_break = self.advance_by(1)? == "0";
+            if result.leading_zeros() < 4 {
+                return Err(self.error("the literal is too large"));
+            }
+            result = result << 4 | self.parse_fixed_length(4)? as usize;
+            if should_break {
                 break;
             }
         }
-        result
-    };
+        Ok(result)
+    }
+}
 
-    let version = parse_fixed_length(pos, 3);
-    let type_id = parse_fixed_length(pos, 3);
+/// Parse a packet inside `depth` others
+fn parse_packet(bits: &mut Bits, depth: usize) -> Result<Packet, ParseError> {
+    if depth > MAX_DEPTH {
+        return Err(bits.error("the packet is nested too deeply"));
+    }
+    let version = bits.parse_fixed_length(3)?;
+    let type_id = bits.parse_fixed_length(3)?;
     let content = match type_id {
         4 => PacketContent::Literal {
-            value: parse_variable_length(pos),
+            value: bits.parse_variable_length()?,
         },
         operator => {
-            let length_type_id = advance_by(pos, 1);
+            let length_type_id = bits.parse_fixed_length(1)?;
             let arguments: Vec<_> = match length_type_id {
-                "0" => {
-                    let bit_length = parse_fixed_length(pos, 15) as usize;
-                    let arguments_end = *pos + bit_length;
+                0 => {
+                    let bit_length = bits.parse_fixed_length(15)? as usize;
+                    let arguments_end = bits.pos + bit_length;
                     let mut arguments = vec![];
-                    while *pos < arguments_end {
-                        arguments.push(parse_packet(input, pos));
+                    while bits.pos < arguments_end {
+                        arguments.push(parse_packet(bits, depth + 1)?);
+                    }
+                    if bits.pos > arguments_end {
+                        return Err(bits.error("the packet overflows its operator"));
                     }
                     arguments
                 }
-                "1" => {
-                    let packet_length = parse_fixed_length(pos, 11) as usize;
+                _ => {
+                    let packet_length = bits.parse_fixed_length(11)? as usize;
                     let mut arguments = Vec::with_capacity(packet_length);
                     for _ in 0..packet_length {
-                        arguments.push(parse_packet(input, pos));
+                        arguments.push(parse_packet(bits, depth + 1)?);
                     }
                     arguments
                 }
-                _ => unreachable!(),
             };
+            let valid = match operator {
+                5..=7 => arguments.len() == 2,
+                _ => !arguments.is_empty(),
+            };
+            if !valid {
+                return Err(bits.error(&format!(
+                    "operator {} cannot take {} packets",
+                    operator,
+                    arguments.len()
+                )));
+            }
             PacketContent::Expression {
                 operator,
                 arguments,
             }
         }
     };
-    Packet { version, content }
+    Ok(Packet { version, content })
 }
 
-fn process(raw: &str) -> Input {
-    let mut pos = 0;
-    let bits: String = raw
-        .chars()
-        .map(|c| {
-            let digit = match c {
-                '0'..='9' => c as u8 - b'0',
-                'A'..='F' => c as u8 - b'A' + 10,
-                _ => unreachable!(),
-            };
-            format!("{:04b}", digit)
-        })
-        .collect();
-    parse_packet(&bits, &mut pos)
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let hex = raw.trim_end();
+    let mut bits = String::with_capacity(hex.len() * 4);
+    for (i, c) in hex.char_indices() {
+        let digit = c.to_digit(16).ok_or_else(|| source.unexpected(&hex[i..]))?;
+        bits.push_str(&format!("{:04b}", digit));
+    }
+    let mut bits = Bits {
+        source,
+        hex,
+        bits,
+        pos: 0,
+    };
+    let packet = parse_packet(&mut bits, 0)?;
+    if evaluate(&packet).is_none() {
+        return Err(bits.source.error(raw, "the expression overflows"));
+    }
+    Ok(packet)
 }
 
-fn p1(input: &Input) -> usize {
+pub fn p1(input: &Input) -> usize {
     let mut sum = 0;
     let mut packet_stack = vec![input];
     while let Some(packet) = packet_stack.pop() {
@@ -123,46 +160,86 @@ fn p1(input: &Input) -> usize {
     sum
 }
 
-fn evaluate(input: &Input) -> usize {
+/// The value of the packet, or `None` if a sum or product overflows
+fn evaluate(input: &Input) -> Option<usize> {
     match &input.content {
-        PacketContent::Literal { value } => *value as usize,
+        PacketContent::Literal { value } => Some(*value),
         PacketContent::Expression {
             operator,
             arguments,
         } => {
-            let mut results_iter = arguments.iter().map(evaluate);
+            let results = arguments.iter().map(evaluate).collect::<Option<Vec<_>>>()?;
+            let mut results_iter = results.into_iter();
             match operator {
-                0 => results_iter.sum(),
-                1 => results_iter.product(),
-                2 => results_iter.min().unwrap(),
-                3 => results_iter.max().unwrap(),
-                5 => (results_iter.next() > results_iter.next()) as usize,
-                6 => (results_iter.next() < results_iter.next()) as usize,
-                7 => (results_iter.next() == results_iter.next()) as usize,
+                0 => results_iter.try_fold(0, usize::checked_add),
+                1 => results_iter.try_fold(1, usize::checked_mul),
+                2 => results_iter.min(),
+                3 => results_iter.max(),
+                5 => Some((results_iter.next() > results_iter.next()) as usize),
+                6 => Some((results_iter.next() < results_iter.next()) as usize),
+                7 => Some((results_iter.next() == results_iter.next()) as usize),
                 _ => unreachable!(),
             }
         }
     }
 }
-fn p2(input: &Input) -> usize {
-    evaluate(input)
+
+pub fn p2(input: &Input) -> usize {
+    // `process` made sure it does not overflow
+    evaluate(input).unwrap()
 }
 
+aoc_core::solution!(Day16, 2021, 16, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 16, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 977);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 16, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 101501020883);
     }
+
+    /// Sum packets holding a single packet each, around a literal 1
+    fn nested(depth: usize) -> String {
+        let mut bits = "000000100000000001".repeat(depth) + "00010000001";
+        while !bits.len().is_multiple_of(4) {
+            bits.push('0');
+        }
+        bits.as_bytes()
+            .chunks(4)
+            .map(|digit| {
+                let digit = std::str::from_utf8(digit).unwrap();
+                format!("{:X}", u8::from_str_radix(digit, 2).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nesting() {
+        assert_eq!(p2(&process(&nested(MAX_DEPTH)).unwrap()), 1);
+        // would overflow the stack
+        let err = process(&nested(100_000)).unwrap_err();
+        assert!(err.to_string().contains("nested too deeply"), "{}", err);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // the product of six 2^16 + 1
+        let err = process("0600A48C210842108401246108421084200").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 1, "the expression overflows"));
+        // a literal of 17 four-bit groups
+        let err = process("D3FFFFFFFFFFFFFFFFFFFDE").unwrap_err();
+        assert!(err.to_string().contains("too large"), "{}", err);
+    }
 }
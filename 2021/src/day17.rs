@@ -1,17 +1,6 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day17.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
-struct Input {
+pub struct Input {
     x_min: i16,
     x_max: i16,
     y_min: i16,
@@ -21,17 +10,33 @@ type Point = (i16, i16);
 
 const START: Point = (0, 0);
 
-fn process(raw: &str) -> Input {
+/// Read `{axis}=min..max`
+fn parse_range(source: &Source, text: &str, axis: &str) -> Result<(i16, i16), ParseError> {
+    let range = source.strip_prefix(text, &format!("{}=", axis))?;
+    let (min_text, max_text) = source.split_once(range, "..")?;
+    let (min, max) = (source.parse(min_text, axis)?, source.parse(max_text, axis)?);
+    if min > max {
+        return Err(source.error(range, format!("the range `{}` is empty", range)));
+    }
+    Ok((min, max))
+}
+
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
     // target area: x=57..116, y=-198..-148
-    let (x_range, y_range) = raw.lines().next().unwrap()[13..].split_once(", ").unwrap();
-    let (x_min_text, x_max_text) = x_range[2..].split_once("..").unwrap();
-    let (y_min_text, y_max_text) = y_range[2..].split_once("..").unwrap();
-    Input {
-        x_min: x_min_text.parse().unwrap(),
-        x_max: x_max_text.parse().unwrap(),
-        y_min: y_min_text.parse().unwrap(),
-        y_max: y_max_text.parse().unwrap(),
+    let area = source.strip_prefix(raw.trim_end(), "target area: ")?;
+    let (x_range, y_range) = source.split_once(area, ", ")?;
+    let (x_min, x_max) = parse_range(&source, x_range, "x")?;
+    let (y_min, y_max) = parse_range(&source, y_range, "y")?;
+    if x_min <= 0 || y_max >= 0 {
+        return Err(source.error(area, "the target must be below and right of the probe"));
     }
+    Ok(Input {
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+    })
 }
 
 fn is_point_within(x: &i16, y: &i16, input: &Input) -> bool {
@@ -62,7 +67,7 @@ fn simulate(input: &Input, vx0: i16, vy0: i16) -> Option<i16> {
         vx -= i16::signum(vx);
     }
 }
-fn p1(input: &Input) -> i16 {
+pub fn p1(input: &Input) -> i16 {
     // Search vx in [0, x_max]
     // when vx > x_max, the trajectory passes x_max after the first step
     // regardless of vy
@@ -80,7 +85,7 @@ fn p1(input: &Input) -> i16 {
         .unwrap()
 }
 
-fn p2(input: &Input) -> usize {
+pub fn p2(input: &Input) -> usize {
     (0..=input.x_max)
         .map(|vx| {
             // Search vy in [y_min, -y_min]
@@ -92,21 +97,24 @@ fn p2(input: &Input) -> usize {
         .sum()
 }
 
+aoc_core::solution!(Day17, 2021, 17, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 17, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 19503);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 17, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 5200);
     }
 }
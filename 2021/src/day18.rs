@@ -1,15 +1,4 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day18.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
 const SN_SIZE: usize = 64;
 const SPLIT_THRESHOLD: u16 = 10;
@@ -35,27 +24,60 @@ const SPLIT_THRESHOLD: u16 = 10;
 /// To get the parent slot of any given index, we can divide the index by 2
 /// To check all need-to-be-exploded pairs, we can iterate slice 32 .. 63
 ///
-type SN = [Option<u16>; SN_SIZE];
+pub type SN = [Option<u16>; SN_SIZE];
 
-fn parse_sn(input: &str) -> SN {
+pub fn parse_sn(input: &str) -> Result<SN, ParseError> {
+    parse_sn_in(&Source::new(input), input)
+}
+
+/// Parse `input`, a line of `source`
+fn parse_sn_in(source: &Source, input: &str) -> Result<SN, ParseError> {
     let mut sn = new_sn();
     let mut pos = 0;
-    parse_pair(input, &mut pos, 1, &mut sn);
-    sn
+    if !input.starts_with('[') {
+        return Err(source.error(input, "expected `[`"));
+    }
+    parse_pair(source, input, &mut pos, 1, &mut sn)?;
+    if pos < input.len() {
+        return Err(source.unexpected(&input[pos..]));
+    }
+    Ok(sn)
+}
+
+fn eat(source: &Source, input: &str, pos: &mut usize, c: char) -> Result<(), ParseError> {
+    if !input[*pos..].starts_with(c) {
+        return Err(source.error(&input[*pos..], format!("expected `{}`", c)));
+    }
+    *pos += 1;
+    Ok(())
 }
 
-fn parse_pair(input: &str, pos: &mut usize, i: usize, sn: &mut SN) {
-    let start = *pos;
-    *pos += 1; // eat [ or number
-    match &input[start..*pos] {
-        "[" => {
-            parse_pair(input, pos, i << 1, sn);
-            *pos += 1; // eat ,
-            parse_pair(input, pos, i << 1 | 1, sn);
-            *pos += 1; // eat ]
+fn parse_pair(
+    source: &Source,
+    input: &str,
+    pos: &mut usize,
+    i: usize,
+    sn: &mut SN,
+) -> Result<(), ParseError> {
+    let rest = &input[*pos..];
+    if i >= SN_SIZE {
+        return Err(source.error(rest, "the pair is nested too deeply"));
+    }
+    match rest.chars().next() {
+        Some('[') => {
+            *pos += 1;
+            parse_pair(source, input, pos, i << 1, sn)?;
+            eat(source, input, pos, ',')?;
+            parse_pair(source, input, pos, i << 1 | 1, sn)?;
+            eat(source, input, pos, ']')?;
+        }
+        Some(c @ '0'..='9') => {
+            *pos += 1;
+            sn[i] = Some(c as u16 - '0' as u16);
         }
-        c => sn[i] = Some(c.parse().unwrap()),
+        _ => return Err(source.unexpected(rest)),
     };
+    Ok(())
 }
 
 fn new_sn() -> SN {
@@ -155,7 +177,8 @@ fn get_first_split(sn: &SN) -> Option<usize> {
     }
 }
 
-fn add(left: &SN, right: &SN) -> SN {
+/// Add two snailfish numbers, then reduce the sum
+pub fn add(left: &SN, right: &SN) -> SN {
     let mut sn = new_sn();
     // merge two SN
     for depth in 0..4 {
@@ -188,7 +211,7 @@ fn add(left: &SN, right: &SN) -> SN {
     sn
 }
 
-fn magnitude(sn: &SN) -> u16 {
+pub fn magnitude(sn: &SN) -> u16 {
     let mut sum = 0;
     for (i, value_option) in sn.iter().enumerate() {
         if let Some(v) = value_option {
@@ -201,13 +224,21 @@ fn magnitude(sn: &SN) -> u16 {
     sum
 }
 
-type Input = Vec<SN>;
+pub type Input = Vec<SN>;
 
-fn process(raw: &str) -> Input {
-    raw.lines().map(parse_sn).collect()
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let result: Input = raw
+        .lines()
+        .map(|line| parse_sn_in(&source, line))
+        .collect::<Result<_, _>>()?;
+    if result.is_empty() {
+        return Err(source.error(raw, "no snailfish number"));
+    }
+    Ok(result)
 }
 
-fn p1(input: &[SN]) -> u16 {
+pub fn p1(input: &[SN]) -> u16 {
     let mut sum = input[0];
     for sn in input.iter().skip(1) {
         sum = add(&sum, sn)
@@ -215,7 +246,7 @@ fn p1(input: &[SN]) -> u16 {
     magnitude(&sum)
 }
 
-fn p2(input: &[SN]) -> u16 {
+pub fn p2(input: &[SN]) -> u16 {
     let mut max = u16::MIN;
     for x in input {
         for y in input {
@@ -225,21 +256,24 @@ fn p2(input: &[SN]) -> u16 {
     max
 }
 
+aoc_core::solution!(Day18, 2021, 18, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 18, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 3987);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 18, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 4500);
     }
 
@@ -264,12 +298,12 @@ mod test {
     #[test]
     fn test_sum() {
         let input = [
-            parse_sn("[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]"),
-            parse_sn("[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]"),
+            parse_sn("[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]").unwrap(),
+            parse_sn("[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]").unwrap(),
         ];
         let mut sum = input[0];
-        for i in 1..input.len() {
-            sum = add(&sum, &input[i]);
+        for sn in &input[1..] {
+            sum = add(&sum, sn);
         }
         assert_eq!(
             debug_print(&sum),
@@ -290,9 +324,9 @@ mod test {
             "[[[5,[7,4]],7],1]",
             "[[[[4,2],2],6],[8,7]]",
         ];
-        let mut sum = parse_sn(input[0]);
-        for i in 1..input.len() {
-            sum = add(&sum, &parse_sn(input[i]));
+        let mut sum = parse_sn(input[0]).unwrap();
+        for sn in &input[1..] {
+            sum = add(&sum, &parse_sn(sn).unwrap());
         }
         assert_eq!(
             debug_print(&sum),
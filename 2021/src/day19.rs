@@ -1,116 +1,69 @@
+use aoc_core::{ParseError, Source};
+use aoc_geom::{rotations, Coord, Point, Rotation, Vector};
 use std::collections::{HashMap, HashSet};
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day19.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let mut input = process(raw);
-    println!("Answer of p1: {}", p1(&mut input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
-type Number = i32;
-
-type Point = [Number; 3];
-
-type Vector = Point;
-
-type Permute = [Vector; 3];
-
-const PERMUTATION: [Permute; 24] = [
-    //
-    [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
-    [[-1, 0, 0], [0, -1, 0], [0, 0, 1]],
-    [[-1, 0, 0], [0, 1, 0], [0, 0, -1]],
-    [[1, 0, 0], [0, -1, 0], [0, 0, -1]],
-    //
-    [[-1, 0, 0], [0, 0, 1], [0, 1, 0]],
-    [[1, 0, 0], [0, 0, -1], [0, 1, 0]],
-    [[1, 0, 0], [0, 0, 1], [0, -1, 0]],
-    [[-1, 0, 0], [0, 0, -1], [0, -1, 0]],
-    //
-    [[0, -1, 0], [1, 0, 0], [0, 0, 1]],
-    [[0, 1, 0], [-1, 0, 0], [0, 0, 1]],
-    [[0, 1, 0], [1, 0, 0], [0, 0, -1]],
-    [[0, -1, 0], [-1, 0, 0], [0, 0, -1]],
-    //
-    [[0, 1, 0], [0, 0, 1], [1, 0, 0]],
-    [[0, -1, 0], [0, 0, -1], [1, 0, 0]],
-    [[0, -1, 0], [0, 0, 1], [-1, 0, 0]],
-    [[0, 1, 0], [0, 0, -1], [-1, 0, 0]],
-    //
-    [[0, 0, 1], [1, 0, 0], [0, 1, 0]],
-    [[0, 0, -1], [-1, 0, 0], [0, 1, 0]],
-    [[0, 0, -1], [1, 0, 0], [0, -1, 0]],
-    [[0, 0, 1], [-1, 0, 0], [0, -1, 0]],
-    //
-    [[0, 0, -1], [0, 1, 0], [1, 0, 0]],
-    [[0, 0, 1], [0, -1, 0], [1, 0, 0]],
-    [[0, 0, 1], [0, 1, 0], [-1, 0, 0]],
-    [[0, 0, -1], [0, -1, 0], [-1, 0, 0]],
-];
+type Point3 = Point<3>;
 
 const OVERLAP_THRESHOLD: usize = 12 * 11;
 
-fn add(lhs: &Point, rhs: &Point) -> Point {
-    [lhs[0] + rhs[0], lhs[1] + rhs[1], lhs[2] + rhs[2]]
+pub struct Scanner {
+    points: Vec<Point3>,
 }
 
-fn sub(lhs: &Point, rhs: &Point) -> Point {
-    [lhs[0] - rhs[0], lhs[1] - rhs[1], lhs[2] - rhs[2]]
-}
-
-fn dot(lhs: &Point, rhs: &Point) -> Number {
-    lhs[0] * rhs[0] + lhs[1] * rhs[1] + lhs[2] * rhs[2]
+#[derive(Debug)]
+struct PointTransform {
+    rotation: Rotation<3>,
+    offset: Vector<3>,
 }
 
-fn rotate(source: &Point, permute: &Permute) -> Point {
-    [
-        dot(&permute[0], source),
-        dot(&permute[1], source),
-        dot(&permute[2], source),
-    ]
+/// Scanners are aligned while parsing, both parts share the alignment
+pub struct Input {
+    beacon_count: usize,
+    /// scanner positions relative to the first scanner
+    positions: Vec<Point3>,
 }
 
-struct Scanner {
-    position: Option<Point>,
-    points: Vec<Point>,
-}
+type VectorWithStart = HashMap<Vector<3>, Point3>;
 
-#[derive(Debug)]
-struct PointTransform<'a> {
-    permute: &'a Permute,
-    offset: Vector,
+fn parse_beacon(source: &Source, text: &str) -> Result<Point3, ParseError> {
+    let mut coords = text.splitn(4, ',');
+    let mut point = Point::ORIGIN;
+    for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+        point[axis] = source.parse(source.field(&mut coords, text, name)?, name)?;
+    }
+    match coords.next() {
+        Some(extra) => Err(source.error(extra, "a beacon has three coordinates")),
+        None => Ok(point),
+    }
 }
 
-type Input = Vec<Scanner>;
-
-type VectorWithStart = HashMap<Vector, Point>;
-
-fn process(raw: &str) -> Input {
-    raw.split("\n\n")
-        .map(|result| Scanner {
-            position: None,
-            points: result
-                .lines()
-                .skip(1)
-                .map(|beacon_text| {
-                    let pos: Vec<Number> = beacon_text
-                        .splitn(3, ',')
-                        .map(|x| x.parse().unwrap())
-                        .collect();
-                    [pos[0], pos[1], pos[2]]
-                })
-                .collect(),
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let mut headers = vec![];
+    let scanners = raw
+        .split("\n\n")
+        .filter(|text| !text.trim().is_empty())
+        .map(|result| {
+            let mut lines = result.lines();
+            let header = source.field(&mut lines, result, "scanner header")?;
+            source.strip_prefix(header, "--- scanner ")?;
+            headers.push(header);
+            Ok(Scanner {
+                points: lines
+                    .map(|beacon_text| parse_beacon(&source, beacon_text))
+                    .collect::<Result<_, _>>()?,
+            })
         })
-        .collect()
+        .collect::<Result<Vec<_>, _>>()?;
+    if scanners.is_empty() {
+        return Err(source.error(raw, "no scanner"));
+    }
+    align(&scanners).map_err(|(i, message)| source.error(headers[i], message))
 }
 
-fn generate_vector_with_start(points: &[Point]) -> VectorWithStart {
+/// The vectors between every two beacons, or `None` when two of them are
+/// the same, which would make the alignment ambiguous
+fn generate_vector_with_start(points: &[Point3]) -> Option<VectorWithStart> {
     let mut result = HashMap::new();
     for i in 0..points.len() {
         // todo: we can optimize here by only computing i < j and derive opposite
@@ -118,44 +71,35 @@ fn generate_vector_with_start(points: &[Point]) -> VectorWithStart {
             if i == j {
                 continue;
             }
-            let vector = sub(&points[j], &points[i]);
+            let vector = points[j] - points[i];
             result.insert(vector, points[i]);
         }
     }
-    // This assertion requires that the vector is not equal to any other vectors
-    assert!(result.len() == points.len() * (points.len() - 1));
-    result
+    (result.len() == points.len() * points.len().saturating_sub(1)).then_some(result)
 }
 
-fn point_transform<'a>(source: &'a Point, transform: &PointTransform) -> Point {
-    let PointTransform { permute, offset } = transform;
-    add(
-        &[
-            dot(&permute[0], source),
-            dot(&permute[1], source),
-            dot(&permute[2], source),
-        ],
-        offset,
-    )
+fn point_transform(source: &Point3, transform: &PointTransform) -> Point3 {
+    let PointTransform { rotation, offset } = transform;
+    rotation.apply(source) + *offset
 }
 
-fn vectors_rotate(source: &HashMap<Vector, Point>, permute: &Permute) -> HashMap<Vector, Point> {
+fn vectors_rotate(source: &VectorWithStart, rotation: &Rotation<3>) -> VectorWithStart {
     source
         .iter()
-        .map(|(vector, &data)| (rotate(vector, permute), data))
+        .map(|(vector, &data)| (rotation.apply(vector), data))
         .collect()
 }
 
-fn vector_starts_transform(source: &mut HashMap<Vector, Point>, transform: &PointTransform) {
+fn vector_starts_transform(source: &mut VectorWithStart, transform: &PointTransform) {
     for (_, data) in source.iter_mut() {
         *data = point_transform(data, transform);
     }
 }
 
 fn get_shared_vector_when_overlapping_threshold_reached(
-    lhs: &HashMap<Vector, Point>,
-    rhs: &HashMap<Vector, Point>,
-) -> Option<Vector> {
+    lhs: &VectorWithStart,
+    rhs: &VectorWithStart,
+) -> Option<Vector<3>> {
     let mut count = 0;
     let mut result = None;
     for &vector in rhs.keys() {
@@ -175,9 +119,10 @@ fn get_shared_vector_when_overlapping_threshold_reached(
 fn try_extend_aligned_vectors(
     aligned_vectors: &mut VectorWithStart,
     unaligned: &VectorWithStart,
-) -> Option<Point> {
-    for permute in PERMUTATION.iter() {
-        let mut transformed_source = vectors_rotate(unaligned, permute);
+    rotations: &[Rotation<3>],
+) -> Option<Point3> {
+    for rotation in rotations {
+        let mut transformed_source = vectors_rotate(unaligned, rotation);
         if let Some(vector) = get_shared_vector_when_overlapping_threshold_reached(
             aligned_vectors,
             &transformed_source,
@@ -186,8 +131,12 @@ fn try_extend_aligned_vectors(
             let aligned_point = aligned_vectors.get(&vector).unwrap();
             // The same beacon with point representation in the unaligned scanner
             let unaligned_point = *transformed_source.get(&vector).unwrap();
-            let offset = sub(aligned_point, &rotate(&unaligned_point, permute));
-            vector_starts_transform(&mut transformed_source, &PointTransform { permute, offset });
+            let offset = *aligned_point - rotation.apply(&unaligned_point);
+            let rotation = *rotation;
+            vector_starts_transform(
+                &mut transformed_source,
+                &PointTransform { rotation, offset },
+            );
             aligned_vectors.extend(&transformed_source);
             return Some(offset);
         }
@@ -195,66 +144,109 @@ fn try_extend_aligned_vectors(
     None
 }
 
-fn p1(input: &mut [Scanner]) -> usize {
-    let mut first_scanner = &mut input[0];
-    first_scanner.position = Some([0, 0, 0]);
-    let aligned_vectors = &mut generate_vector_with_start(&input[0].points);
-    // push other vectors to vector starts
+/// Align every scanner with the first one
+///
+/// Fails with the index of a scanner that sees two pairs of beacons the same
+/// way, or that never overlaps enough with the aligned ones.
+fn align(input: &[Scanner]) -> Result<Input, (usize, &'static str)> {
+    let rotations = rotations::<3>();
+    let mut positions = vec![Point::ORIGIN; input.len()];
     let mut vector_starts = vec![];
-    for scanner in input.iter().skip(1) {
-        vector_starts.push(generate_vector_with_start(&scanner.points));
+    for (i, scanner) in input.iter().enumerate() {
+        vector_starts.push(
+            generate_vector_with_start(&scanner.points)
+                .ok_or((i, "two pairs of beacons are the same vector apart"))?,
+        );
     }
+    let aligned_vectors = &mut vector_starts[0].clone();
 
     let mut aligned_set = HashSet::from([0]);
     loop {
+        let aligned_count = aligned_set.len();
         for i in 1..input.len() {
             if aligned_set.contains(&i) {
                 continue;
             }
-            if let Some(point) = try_extend_aligned_vectors(aligned_vectors, &vector_starts[i - 1])
+            if let Some(point) =
+                try_extend_aligned_vectors(aligned_vectors, &vector_starts[i], &rotations)
             {
-                input[i].position = Some(point);
+                positions[i] = point;
                 aligned_set.insert(i);
             }
         }
         if aligned_set.len() == input.len() {
             break;
         }
+        if aligned_set.len() == aligned_count {
+            let i = (1..input.len()).find(|i| !aligned_set.contains(i)).unwrap();
+            return Err((i, "the scanner overlaps no aligned scanner"));
+        }
     }
 
-    // sum the unique vector starting points
-    aligned_vectors.values().collect::<HashSet<_>>().len()
+    Ok(Input {
+        // sum the unique vector starting points
+        beacon_count: aligned_vectors.values().collect::<HashSet<_>>().len(),
+        positions,
+    })
 }
 
-fn norm_l1(vector: &Vector) -> Number {
-    vector[0].abs() + vector[1].abs() + vector[2].abs()
+pub fn p1(input: &Input) -> usize {
+    input.beacon_count
 }
 
-fn p2(input: &[Scanner]) -> Number {
-    let mut max_distance = Number::MIN;
-    for i in 0..input.len() {
-        for j in i + 1..input.len() {
-            max_distance = Number::max(
-                max_distance,
-                norm_l1(&sub(
-                    &input[j].position.unwrap(),
-                    &input[i].position.unwrap(),
-                )),
-            );
+pub fn p2(input: &Input) -> Coord {
+    let positions = &input.positions;
+    let mut max_distance = Coord::MIN;
+    for i in 0..positions.len() {
+        for j in i + 1..positions.len() {
+            max_distance = max_distance.max(positions[j].manhattan_distance(&positions[i]));
         }
     }
     max_distance
 }
 
+aoc_core::solution!(Day19, 2021, 19, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
+
+    #[test]
+    fn test_p1() {
+        let raw = aoc_input::read(2021, 19, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 512);
+    }
 
     #[test]
-    fn test_p1_and_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let mut input = process(raw);
-        assert_eq!(p1(&mut input), 512);
+    fn test_p2() {
+        let raw = aoc_input::read(2021, 19, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 16802);
     }
+
+    #[test]
+    fn test_unalignable() {
+        let error = |raw| process(raw).err();
+        assert_eq!(error(""), Some(ParseError::new(1, 1, "no scanner")));
+        let raw = "--- scanner 0 ---\n0,0,0\n1,0,0\n\n--- scanner 1 ---\n5,5,5\n";
+        assert_eq!(
+            error(raw),
+            Some(ParseError::new(
+                5,
+                1,
+                "the scanner overlaps no aligned scanner"
+            ))
+        );
+        let raw = "--- scanner 0 ---\n0,0,0\n1,0,0\n2,0,0\n";
+        assert_eq!(
+            error(raw),
+            Some(ParseError::new(
+                1,
+                1,
+                "two pairs of beacons are the same vector apart"
+            ))
+        );
+    }
 }
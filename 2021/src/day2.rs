@@ -1,17 +1,4 @@
-use std::str::FromStr;
-
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day2.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
 #[derive(Debug)]
 enum MoveOperation {
@@ -21,36 +8,30 @@ enum MoveOperation {
 }
 
 #[derive(Debug)]
-struct Move {
+pub struct Move {
     value: i32,
     op: MoveOperation,
 }
 
-#[derive(Debug, Clone)]
-struct ParseMoveError;
-
-impl FromStr for Move {
-    type Err = ParseMoveError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (op, val) = s.split_once(' ').unwrap();
-        Ok(Move {
-            value: val.parse().map_err(|_| ParseMoveError)?,
-            op: match op {
-                "forward" => MoveOperation::Forward,
-                "down" => MoveOperation::Down,
-                "up" => MoveOperation::Up,
-                _ => unreachable!(),
-            },
-        })
-    }
+fn parse_move(source: &Source, s: &str) -> Result<Move, ParseError> {
+    let (op, val) = source.split_once(s, " ")?;
+    Ok(Move {
+        value: source.parse(val, "distance")?,
+        op: match op {
+            "forward" => MoveOperation::Forward,
+            "down" => MoveOperation::Down,
+            "up" => MoveOperation::Up,
+            _ => return Err(source.error(op, format!("invalid command `{}`", op))),
+        },
+    })
 }
 
-fn process(raw: &str) -> Vec<Move> {
-    raw.lines().map(|n| n.parse().unwrap()).collect()
+pub fn process(raw: &str) -> Result<Vec<Move>, ParseError> {
+    let source = Source::new(raw);
+    raw.lines().map(|n| parse_move(&source, n)).collect()
 }
 
-fn p1(input: &[Move]) -> i32 {
+pub fn p1(input: &[Move]) -> i32 {
     let mut hoz = 0;
     let mut depth = 0;
     for mov in input {
@@ -64,7 +45,7 @@ fn p1(input: &[Move]) -> i32 {
     hoz * depth
 }
 
-fn p2(input: &[Move]) -> i32 {
+pub fn p2(input: &[Move]) -> i32 {
     let mut hoz = 0;
     let mut depth = 0;
     let mut aim = 0;
@@ -82,21 +63,24 @@ fn p2(input: &[Move]) -> i32 {
     hoz * depth
 }
 
+aoc_core::solution!(Day2, 2021, 2, Vec<Move>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 2, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 1690020);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 2, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 1408487760);
     }
 }
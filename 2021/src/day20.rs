@@ -1,23 +1,8 @@
-use std::collections::HashSet;
+use aoc_core::{ParseError, Source};
+use aoc_grid::Grid;
+use aoc_image::{Recorder, Rgb, BLACK, WHITE};
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day20.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
-type Number = usize;
-
-type Point = [Number; 2];
-
-type Algorithm = Vec<u8>;
+type Algorithm = Vec<bool>;
 
 // Delta array ordererd as
 // 8 7 6
@@ -35,133 +20,153 @@ const DELTA: [[isize; 2]; 9] = [
     [-1, -1],
 ];
 
-const PADDING: Number = 1;
+const PADDING: usize = 1;
 const ALGORITHM_SIZE: usize = 512;
 
-// An image is a HashSet of lit pixels
-type Image = Vec<Vec<u8>>;
+// An image is a grid of lit pixels
+type Image = Grid<bool>;
 
-struct Input {
+pub struct Input {
     algorithm: Algorithm,
     image: Image,
 }
 
-fn process(raw: &str) -> Input {
-    let (algorithm_text, image_rep) = raw.split_once("\n\n").unwrap();
+fn pixel(ch: char) -> Option<bool> {
+    match ch {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let (algorithm_text, image_rep) = source.split_once(raw, "\n\n")?;
+    // the algorithm of the example is wrapped over several lines
     let algorithm = algorithm_text
-        .chars()
-        .filter_map(|ch| match ch {
-            '#' => Some(1),
-            '.' => Some(0),
-            _ => None,
-        })
-        .collect::<Vec<_>>();
-    assert_eq!(algorithm.len(), ALGORITHM_SIZE);
-
-    let mut image = HashSet::new();
-    for (j, line) in image_rep.lines().enumerate() {
-        for (i, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                image.insert([i as Number, j as Number]);
-            }
-        }
+        .char_indices()
+        .filter(|&(_, ch)| ch != '\n')
+        .map(|(i, ch)| pixel(ch).ok_or_else(|| source.unexpected(&algorithm_text[i..])))
+        .collect::<Result<Vec<_>, _>>()?;
+    if algorithm.len() != ALGORITHM_SIZE {
+        return Err(source.error(
+            algorithm_text,
+            format!(
+                "the algorithm has {} pixels instead of {}",
+                algorithm.len(),
+                ALGORITHM_SIZE
+            ),
+        ));
     }
-    let image = image_rep
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|ch| match ch {
-                    '#' => 1,
-                    '.' => 0,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
-
-    Input { algorithm, image }
+
+    let image = Grid::try_parse(image_rep, pixel).map_err(|err| source.relocate(image_rep, err))?;
+
+    Ok(Input { algorithm, image })
 }
 
+/// Whether the pixel at `pos` of the enhanced image, which is padded by
+/// PADDING on each side, is light
 fn enhanced_pixel_is_light(
-    [x0, y0]: Point,
-    image: &[Vec<u8>],
-    algorithm: &[u8],
-    width: usize,
-    height: usize,
+    (x, y): (usize, usize),
+    image: &Image,
+    algorithm: &[bool],
     border_is_light: bool,
-) -> u8 {
+) -> bool {
     let mut index = 0;
     for (i, [dx, dy]) in DELTA.iter().enumerate() {
-        let neighbor = [
-            x0 as isize + dx - PADDING as isize,
-            y0 as isize + dy - PADDING as isize,
-        ];
-        let [x, y] = neighbor;
-        if x < 0 || x >= width as isize || y < 0 || y >= height as isize {
-            if border_is_light {
-                index |= 1 << i
-            }
-        } else if image[y as usize][x as usize] == 1 {
+        let delta = (dx - PADDING as isize, dy - PADDING as isize);
+        let light = match image.offset((x, y), delta) {
+            Some(neighbor) => image[neighbor],
+            None => border_is_light,
+        };
+        if light {
             index |= 1 << i
         }
     }
     algorithm[index]
 }
 
-fn enhance(image: &[Vec<u8>], algorithm: &[u8], border_is_light: &mut bool) -> Image {
-    let height = image.len();
-    let width = image[0].len();
-
-    let mut output = vec![vec![0; width + 2 * PADDING]; height + 2 * PADDING];
-    for (y, line) in output.iter_mut().enumerate() {
-        for (x, pixel) in line.iter_mut().enumerate() {
-            *pixel =
-                enhanced_pixel_is_light([x, y], image, algorithm, width, height, *border_is_light);
-        }
-    }
+fn enhance(image: &Image, algorithm: &[bool], border_is_light: &mut bool) -> Image {
+    let output = Grid::from_fn(
+        image.width() + 2 * PADDING,
+        image.height() + 2 * PADDING,
+        |pos| enhanced_pixel_is_light(pos, image, algorithm, *border_is_light),
+    );
 
     // flip border pixels accoriding to algorithms
-    *border_is_light = (algorithm[0] == 1 && !*border_is_light)
-        || (algorithm[ALGORITHM_SIZE - 1] == 1 && *border_is_light);
+    *border_is_light =
+        (algorithm[0] && !*border_is_light) || (algorithm[ALGORITHM_SIZE - 1] && *border_is_light);
     output
 }
 
-fn count_enhanced_light_pixels(input: &Input, round: usize) -> usize {
+/// Enhance the image `round` times, `on_step` sees the input image as step 0
+/// then every enhanced one
+fn enhance_times(input: &Input, round: usize, mut on_step: impl FnMut(usize, &Image)) -> Image {
     let mut border_is_light = false;
     let mut enhanced = input.image.clone();
-    for _ in 0..round {
+    on_step(0, &enhanced);
+    for step in 1..=round {
         enhanced = enhance(&enhanced, &input.algorithm, &mut border_is_light);
+        on_step(step, &enhanced);
     }
     enhanced
-        .iter()
-        .flat_map(|line| line.iter())
-        .filter(|&&ch| ch == 1)
-        .count()
 }
 
-fn p1(input: &Input) -> usize {
+fn count_enhanced_light_pixels(input: &Input, round: usize) -> usize {
+    let enhanced = enhance_times(input, round, |_, _| {});
+    enhanced.values().filter(|&&light| light).count()
+}
+
+pub fn p1(input: &Input) -> usize {
     count_enhanced_light_pixels(input, 2)
 }
 
-fn p2(input: &Input) -> usize {
+pub fn p2(input: &Input) -> usize {
     count_enhanced_light_pixels(input, 50)
 }
 
+/// The image enhanced for part 2
+pub fn render(input: &Input) -> aoc_image::Image {
+    enhance_times(input, 50, |_, _| {}).to_image(light_color)
+}
+
+/// Record the 50 enhancements of part 2, the images growing at each step
+pub fn animate(input: &Input, recorder: &mut Recorder) {
+    enhance_times(input, 50, |step, image| {
+        recorder.record(
+            step,
+            || image.render(|&light| if light { '#' } else { '.' }),
+            || image.to_image(light_color),
+        )
+    });
+}
+
+fn light_color(&light: &bool) -> Rgb {
+    if light {
+        WHITE
+    } else {
+        BLACK
+    }
+}
+
+aoc_core::solution!(Day20, 2021, 20, Input, render, animate);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 20, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 5306);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 20, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 17497);
     }
 }
@@ -1,34 +1,31 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day21.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
 const PAWN_MAX: usize = 10;
 const DIE_MAX_P1: usize = 100;
 
-type Input = [usize; 2];
+pub type Input = [usize; 2];
 
-fn process(raw: &str) -> Input {
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
     let mut lines = raw.lines();
-    // normalize pawn to 0-based
-    let player0 = lines.next().unwrap().chars().last().unwrap() as usize - b'0' as usize - 1;
-    let player1 = lines.next().unwrap().chars().last().unwrap() as usize - b'0' as usize - 1;
-    [player0, player1]
+    let mut players = [0; 2];
+    for (i, player) in players.iter_mut().enumerate() {
+        let line = source.field(&mut lines, raw, &format!("player {}", i + 1))?;
+        let (_, position) = source.split_once(line, "starting position: ")?;
+        // normalize pawn to 0-based
+        *player = match source.parse(position, "position")? {
+            p @ 1..=PAWN_MAX => p - 1,
+            _ => return Err(source.error(position, format!("`{}` is not on the track", position))),
+        };
+    }
+    Ok(players)
 }
 
 fn next_pawn(pawn: usize, rhs: usize) -> usize {
-    (pawn + PAWN_MAX as usize + rhs) % PAWN_MAX as usize
+    (pawn + PAWN_MAX + rhs) % PAWN_MAX
 }
 
-fn p1(input: &Input) -> usize {
+pub fn p1(input: &Input) -> usize {
     let (mut die_count, mut next_die) = (0, 0);
     let mut score = [0, 0];
     let mut pawn = *input;
@@ -84,27 +81,30 @@ fn problem(
     }
 }
 
-fn p2(input: &Input) -> usize {
+pub fn p2(input: &Input) -> usize {
     let mut cache = vec![vec![[[None; PAWN_MAX]; PAWN_MAX]; WIN]; WIN];
     let winner_count = problem(0, 0, input[0], input[1], &mut cache);
     winner_count.into_iter().max().unwrap()
 }
 
+aoc_core::solution!(Day21, 2021, 21, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 21, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 925605);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 21, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 486638407378784);
     }
 }
@@ -1,88 +1,37 @@
-use std::num::ParseIntError;
-use std::str::FromStr;
+use aoc_core::{ParseError, Source};
+use aoc_geom::{Aabb, BoxSet, Coord};
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day22.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
-type Number = isize;
-const DIMENSION: usize = 3;
-type Point = [Number; DIMENSION];
+pub type Number = Coord;
 
-#[derive(Clone)]
-struct Cube {
-    /// Assumption: start[0] < end[0], start[1] < end[1], start[2] < end[2]
-    start: Point,
-    end: Point,
-}
-
-impl Cube {
-    fn intersect(self: &Cube, other: &Cube) -> Option<Self> {
-        let mut intersect_cube = Cube {
-            start: [0; 3],
-            end: [0; 3],
-        };
-        for i in 0..DIMENSION {
-            if let Some([start, end]) = interval_intersect(
-                &[self.start[i], self.end[i]],
-                &[other.start[i], other.end[i]],
-            ) {
-                intersect_cube.start[i] = start;
-                intersect_cube.end[i] = end;
-            } else {
-                return None;
-            }
-        }
-        Some(intersect_cube)
-    }
+/// A cuboid including both corners
+pub type Cube = Aabb<3>;
 
-    fn volume(self: &Cube) -> usize {
-        (0..DIMENSION)
-            .into_iter()
-            .map(|i| (self.end[i] - self.start[i]) as usize + 1)
-            .product()
-    }
+/// Parse a cuboid such as `x=0..5,y=-1..12,z=-104834..13456`
+pub fn parse_cube(s: &str) -> Result<Cube, ParseError> {
+    parse_cube_in(&Source::new(s), s)
 }
 
-impl FromStr for Cube {
-    type Err = ParseIntError;
-    // example x=0..5,y=-1..12,z=-104834..13456
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut cube = Self {
-            start: [0; 3],
-            end: [0; 3],
-        };
-        for (i, coordinate_text) in s.split(',').enumerate() {
-            let (start_text, end_text) = coordinate_text[2..].split_once("..").unwrap();
-            let (start, end) = (start_text.parse()?, end_text.parse()?);
-            cube.start[i] = start;
-            cube.end[i] = end;
+/// Parse `s`, a slice of `source`
+fn parse_cube_in(source: &Source, s: &str) -> Result<Cube, ParseError> {
+    let mut start = [0; 3];
+    let mut end = [0; 3];
+    let mut coordinates = s.splitn(3, ',');
+    for (i, axis) in ["x", "y", "z"].into_iter().enumerate() {
+        let coordinate_text = source.field(&mut coordinates, s, axis)?;
+        let range = source.strip_prefix(coordinate_text, &format!("{}=", axis))?;
+        let (start_text, end_text) = source.split_once(range, "..")?;
+        start[i] = source.parse(start_text, axis)?;
+        end[i] = source.parse(end_text, axis)?;
+        if start[i] > end[i] {
+            return Err(source.error(range, format!("the range `{}` is empty", range)));
         }
-        Ok(cube)
     }
+    Ok(Cube::new(start, end))
 }
 
-/// compute the intersect interval between left [a, b] and right [c, d]
-fn interval_intersect(left: &[Number; 2], right: &[Number; 2]) -> Option<[Number; 2]> {
-    if left[1] < right[0] || right[1] < left[0] {
-        None
-    } else {
-        Some([left[0].max(right[0]), left[1].min(right[1])])
-    }
-}
-
-struct Step {
-    on: bool,
-    cube: Cube,
+pub struct Step {
+    pub on: bool,
+    pub cube: Cube,
 }
 
 /// Building block of the intersection forest.
@@ -98,7 +47,7 @@ struct Step {
 /// For every pair, they share an intersection cube, except C3 and C4.
 /// Assuming C1 is on, C2 is off, C3 is on, C4 is on, the intersection forest is
 ///
-/// ```
+/// ```text
 /// C1 ─ C1 ∩ C2 ─ C1 ∩ C2 ∩ C3
 ///             └─ C1 ∩ C2 ∩ C4
 ///   └─ C1 ∩ C3
@@ -127,18 +76,15 @@ fn apply_intersection(node: &mut Node, cube: &Cube) {
 }
 
 fn build_intersection_forest(reboot_steps: &[&Step]) -> Vec<Node> {
-    let mut result = vec![Node {
-        cube: reboot_steps[0].cube.clone(),
-        children: vec![],
-    }];
-
-    for &step in reboot_steps.iter().skip(1) {
+    // leading off steps have nothing to turn off
+    let mut result = vec![];
+    for &step in reboot_steps {
         for node in result.iter_mut() {
             apply_intersection(node, &step.cube);
         }
         if step.on {
             result.push(Node {
-                cube: step.cube.clone(),
+                cube: step.cube,
                 children: vec![],
             })
         }
@@ -163,10 +109,10 @@ fn count_enabled_grids(forest: &[Node]) -> usize {
     while let Some(State { node, should_add }) = stack.pop() {
         if should_add {
             // A, A∩B∩C, A∩B∩C∩D∩E, ...
-            sum += node.cube.volume();
+            sum += node.cube.volume() as usize;
         } else {
             // A∩B, A∩B∩C∩D, ...
-            sum -= node.cube.volume();
+            sum -= node.cube.volume() as usize;
         }
 
         for child in &node.children {
@@ -179,66 +125,156 @@ fn count_enabled_grids(forest: &[Node]) -> usize {
     sum
 }
 
-type Input = Vec<Step>;
+/// Count the cubes left on with a [`BoxSet`] instead of the intersection
+/// forest
+pub fn count_with_box_set(reboot_steps: &[&Step]) -> usize {
+    let mut cubes = BoxSet::new();
+    for step in reboot_steps {
+        if step.on {
+            cubes.insert(&step.cube);
+        } else {
+            cubes.remove(&step.cube);
+        }
+    }
+    cubes.volume() as usize
+}
 
-fn process(raw: &str) -> Input {
-    let result: Vec<_> = raw
-        .lines()
+pub type Input = Vec<Step>;
+
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    raw.lines()
         .map(|line| {
-            let (on_text, cuboid_text) = line.split_once(' ').unwrap();
+            let (on_text, cuboid_text) = source.split_once(line, " ")?;
             let on = match on_text {
                 "on" => true,
                 "off" => false,
-                _ => unreachable!(),
+                _ => return Err(source.error(on_text, format!("invalid state `{}`", on_text))),
             };
-            Step {
+            Ok(Step {
                 on,
-                cube: cuboid_text.parse().unwrap(),
-            }
+                cube: parse_cube_in(&source, cuboid_text)?,
+            })
         })
-        .collect();
-    // otherwise we have to discard the leading off instructions
-    assert!(result[0].on);
-    result
+        .collect()
 }
 
-// L∞ norm
-fn norm_linf(point: &Point) -> isize {
-    point.iter().map(|x| x.abs()).max().unwrap()
-}
+/// The steps within the initialization region
+fn p1_steps(input: &[Step]) -> Vec<&Step> {
+    const P1_BOUND: Number = 50;
 
-fn p1(input: &[Step]) -> usize {
-    const P1_BOUND: isize = 50;
-
-    let p1_input: Vec<_> = input
+    input
         .iter()
         .filter(|&step| {
-            norm_linf(&step.cube.start) <= P1_BOUND && norm_linf(&step.cube.end) <= P1_BOUND
+            step.cube.min().chebyshev() <= P1_BOUND && step.cube.max().chebyshev() <= P1_BOUND
         })
-        .collect();
-    count_enabled_grids(&build_intersection_forest(&p1_input))
+        .collect()
+}
+
+pub fn p1(input: &[Step]) -> usize {
+    count_enabled_grids(&build_intersection_forest(&p1_steps(input)))
 }
 
-fn p2(input: &[Step]) -> usize {
+pub fn p2(input: &[Step]) -> usize {
     let p2_input: Vec<_> = input.iter().collect();
     count_enabled_grids(&build_intersection_forest(&p2_input))
 }
 
+aoc_core::solution!(Day22, 2021, 22, Input);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_geom::Point;
+    use aoc_input::Variant;
+    use aoc_rand::Rng;
+    use std::collections::HashSet;
+
+    /// Turn every cube on and off one by one
+    fn naive(steps: &[Step]) -> usize {
+        let mut on = HashSet::new();
+        for step in steps {
+            for point in step.cube.points() {
+                if step.on {
+                    on.insert(point);
+                } else {
+                    on.remove(&point);
+                }
+            }
+        }
+        on.len()
+    }
+
+    fn random_step(rng: &mut Rng, on: bool) -> Step {
+        let mut min = [0; 3];
+        let mut max = [0; 3];
+        for axis in 0..3 {
+            min[axis] = rng.range(-8..=8);
+            max[axis] = min[axis] + rng.range(0..=6);
+        }
+        Step {
+            on,
+            cube: Cube::new(Point::new(min), Point::new(max)),
+        }
+    }
+
+    const EXAMPLE: &str = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 22, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 650099);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 22, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 1254011191104293);
     }
+
+    #[test]
+    fn test_leading_off_steps() {
+        let input = process("off x=0..1,y=0..0,z=0..0\non x=0..2,y=0..0,z=0..0").unwrap();
+        assert_eq!((p1(&input), p2(&input)), (3, 3));
+        // only the off step is within the initialization region
+        let input = process("on x=-100..100,y=0..0,z=0..0\noff x=0..1,y=0..0,z=0..0").unwrap();
+        assert_eq!((p1(&input), p2(&input)), (0, 199));
+        let input = process("on x=-100..100,y=0..0,z=0..0").unwrap();
+        assert_eq!(p1(&input), 0);
+    }
+
+    #[test]
+    fn test_box_set() {
+        let input = process(EXAMPLE).unwrap();
+        let steps: Vec<_> = input.iter().collect();
+        assert_eq!(count_with_box_set(&steps), 39);
+        assert_eq!(count_enabled_grids(&build_intersection_forest(&steps)), 39);
+
+        let raw = aoc_input::read(2021, 22, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(count_with_box_set(&p1_steps(&input)), 650099);
+        let steps: Vec<_> = input.iter().collect();
+        assert_eq!(count_with_box_set(&steps), 1254011191104293);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = Rng::new(22);
+        for _ in 0..200 {
+            let mut steps = vec![random_step(&mut rng, true)];
+            for _ in 0..rng.below(10) {
+                let on = rng.chance(1, 2);
+                steps.push(random_step(&mut rng, on));
+            }
+            let expected = naive(&steps);
+            assert_eq!(p2(&steps), expected);
+            let steps: Vec<_> = steps.iter().collect();
+            assert_eq!(count_with_box_set(&steps), expected);
+        }
+    }
 }
@@ -1,20 +1,14 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use aoc_core::{ParseError, Source};
+use aoc_search::Found;
 use std::fmt::{Display, Formatter};
 use std::mem::swap;
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day23.txt"
-    };
-}
-
 const HALLWAY_LEN: usize = 11;
 const ROOM_NUM: usize = 4;
 
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum Amphipod {
+pub enum Amphipod {
     A = 0,
     B = 1,
     C = 2,
@@ -44,11 +38,7 @@ impl From<u8> for Amphipod {
 }
 
 fn abs_sub(left: usize, right: usize) -> usize {
-    if left > right {
-        left - right
-    } else {
-        right - left
-    }
+    left.abs_diff(right)
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -118,7 +108,7 @@ impl<const R: usize> State<R> {
     }
 
     fn is_above_room(&self, x: usize) -> bool {
-        x >= 2 && (x - 2) % 2 == 0 && ((x - 2) / 2) < ROOM_NUM
+        x >= 2 && (x - 2).is_multiple_of(2) && ((x - 2) / 2) < ROOM_NUM
     }
 
     // return the first member that could exit the room
@@ -272,75 +262,54 @@ impl<const R: usize> State<R> {
     }
 }
 
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
-type Input = Vec<Amphipod>;
-
-fn process(raw: &str) -> Input {
-    raw.chars()
-        .filter_map(|ch| match ch {
-            'A'..='D' => Some(Amphipod::from(ch as u8 - b'A')),
-            _ => None,
-        })
-        .collect()
-}
-
-#[derive(Eq, PartialEq)]
-struct QueueItem {
-    encoded: u64,
-    cost: usize,
-}
-
-impl Ord for QueueItem {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
+pub type Input = Vec<Amphipod>;
+
+/// Read the amphipods of the burrow row by row, ignoring the walls
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    let source = Source::new(raw);
+    let count = 2 * ROOM_NUM;
+    let mut amphipods = Vec::with_capacity(count);
+    for (i, ch) in raw.char_indices() {
+        match ch {
+            'A'..='D' if amphipods.len() == count => {
+                return Err(source.error(&raw[i..], "there are too many amphipods"))
+            }
+            'A'..='D' => amphipods.push(Amphipod::from(ch as u8 - b'A')),
+            '#' | '.' | ' ' | '\n' => {}
+            _ => return Err(source.unexpected(&raw[i..])),
+        }
+    }
+    if amphipods.len() < count {
+        return Err(source.error_after(
+            raw.trim_end(),
+            format!("{} amphipods instead of {}", amphipods.len(), count),
+        ));
     }
+    Ok(amphipods)
 }
 
-// `PartialOrd` needs to be implemented as well.
-impl PartialOrd for QueueItem {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+/// A* search over encoded states, returns the cheapest sequence of moves
+fn search<const R: usize>(initial_state: State<R>) -> Found<u64, usize> {
+    let goal = State::<R>::goal().encode();
+    aoc_search::astar(
+        initial_state.encode(),
+        |&encoded| {
+            State::<R>::from(encoded)
+                .next_moves()
+                .into_iter()
+                .map(|(state, cost)| (state.encode(), cost))
+        },
+        |&encoded| State::<R>::from(encoded).heuristics(),
+        |&encoded| encoded == goal,
+    )
+    .expect("the amphipods can always be organized")
 }
 
-/// A* Search
 fn solve<const R: usize>(initial_state: State<R>) -> usize {
-    let mut g_scores = HashMap::from([(initial_state.encode(), 0usize)]);
-    let mut heap = BinaryHeap::from(vec![QueueItem {
-        encoded: initial_state.encode(),
-        cost: 0,
-    }]);
-
-    while let Some(QueueItem { encoded, cost }) = heap.pop() {
-        let current_dist = g_scores[&encoded];
-        let current_state = State::<R>::from(encoded);
-
-        if encoded == State::<R>::goal().encode() {
-            return cost;
-        }
-
-        for (state, cost) in current_state.next_moves() {
-            let new_cost = current_dist + cost;
-            let encoded_state = state.encode();
-            if new_cost < *g_scores.get(&encoded_state).unwrap_or(&usize::MAX) {
-                g_scores.insert(encoded_state, new_cost);
-                heap.push(QueueItem {
-                    encoded: encoded_state,
-                    cost: new_cost + state.heuristics(),
-                });
-            }
-        }
-    }
-    unreachable!()
+    search(initial_state).cost
 }
 
-fn p1(input: &[Amphipod]) -> usize {
+pub fn p1(input: &[Amphipod]) -> usize {
     let initial_state = State {
         hallway: [None; HALLWAY_LEN],
         rooms: [
@@ -353,7 +322,7 @@ fn p1(input: &[Amphipod]) -> usize {
     solve(initial_state)
 }
 
-fn p2(input: &[Amphipod]) -> usize {
+pub fn p2(input: &[Amphipod]) -> usize {
     // #D#C#B#A#
     // #D#B#A#C#
     let initial_state = State {
@@ -417,16 +386,13 @@ impl<const R: usize> Display for State<R> {
     }
 }
 
+aoc_core::solution!(Day23, 2021, 23, Input);
+
 #[cfg(test)]
 mod test {
 
-    macro_rules! SAMPLE_PATH {
-        () => {
-            "../data/day23.dbg.txt"
-        };
-    }
-
     use super::*;
+    use aoc_input::Variant;
 
     fn create_empty_state<const R: usize>() -> State<R> {
         State {
@@ -437,7 +403,7 @@ mod test {
 
     #[test]
     fn test_encode_decode_roundtrip() {
-        let input = process(include_str!(DATA_PATH!()));
+        let input = process(&aoc_input::read(2021, 23, Variant::Input).unwrap()).unwrap();
         let state = State {
             hallway: [None; HALLWAY_LEN],
             rooms: [
@@ -477,7 +443,7 @@ mod test {
         state.hallway[5] = Some(Amphipod::A);
         state.hallway[7] = Some(Amphipod::B);
         assert!(state.is_hallway_clear(5, 4));
-        assert_eq!(state.is_hallway_clear(5, 7), false);
+        assert!(!state.is_hallway_clear(5, 7));
     }
 
     #[test]
@@ -612,7 +578,7 @@ mod test {
         state.rooms[0][0] = Some(Amphipod::B);
         assert_eq!(
             state.heuristics(),
-            Amphipod::B.energy() * 3 + Amphipod::A.energy() * 1
+            Amphipod::B.energy() * 3 + Amphipod::A.energy()
         );
 
         let mut state = State::<2>::goal();
@@ -633,31 +599,55 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_search_path() {
+        let raw = aoc_input::read(2021, 23, Variant::Example).unwrap();
+        let input = process(&raw).unwrap();
+        let initial_state = State {
+            hallway: [None; HALLWAY_LEN],
+            rooms: [0, 1, 2, 3].map(|i| [Some(input[i]), Some(input[i + ROOM_NUM])]),
+        };
+        let found = search(initial_state);
+        assert_eq!(found.path[0], initial_state.encode());
+        assert_eq!(*found.goal(), State::<2>::goal().encode());
+        // every step is a legal move and their costs add up
+        let cost: usize = found
+            .path
+            .windows(2)
+            .map(|step| {
+                let moves = State::<2>::from(step[0]).next_moves();
+                let (_, cost) = moves.iter().find(|(s, _)| s.encode() == step[1]).unwrap();
+                *cost
+            })
+            .sum();
+        assert_eq!(cost, 12521);
+    }
+
     #[test]
     fn test_p1_sample() {
-        let raw = include_str!(SAMPLE_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 23, Variant::Example).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 12521);
     }
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 23, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 13558);
     }
 
     #[test]
     fn test_p2_sample() {
-        let raw = include_str!(SAMPLE_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 23, Variant::Example).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 44169);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 23, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 56982);
     }
 }
@@ -1,21 +1,41 @@
-use std::str::FromStr;
+use aoc_core::{ParseError, Source};
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day24.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
-
-fn process(raw: &str) -> Vec<Instruction> {
-    let result: Vec<_> = raw.lines().map(|l| l.parse().unwrap()).collect();
-    result
+pub fn process(raw: &str) -> Result<Vec<Instruction>, ParseError> {
+    let source = Source::new(raw);
+    let lines: Vec<_> = raw.lines().collect();
+    let result = lines
+        .iter()
+        .map(|l| parse_instruction(&source, l))
+        .collect::<Result<Vec<_>, _>>()?;
+    // the solver reads its parameters at fixed places of each digit section
+    if result.is_empty() || !result.len().is_multiple_of(DIGIT_LEN) {
+        return Err(source.error_after(
+            raw.trim_end(),
+            format!(
+                "{} instructions cannot make {} sections",
+                result.len(),
+                DIGIT_LEN
+            ),
+        ));
+    }
+    let section_len = result.len() / DIGIT_LEN;
+    for (index, instruction) in result.iter().enumerate() {
+        let valid = match index % section_len {
+            0 => matches!(instruction, Instruction::Inp(_)),
+            5 | 15 => matches!(instruction, Instruction::Addi(..)),
+            _ => true,
+        };
+        if !valid {
+            return Err(source.error(
+                lines[index],
+                format!(
+                    "unexpected instruction `{}` in a digit section",
+                    lines[index]
+                ),
+            ));
+        }
+    }
+    Ok(result)
 }
 
 // The solver does not rely on the ALU, but it is fun to implement it
@@ -25,31 +45,25 @@ type Immediate = Register;
 
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum RegisterLabel {
+pub enum RegisterLabel {
     W = 0,
     X = 1,
     Y = 2,
     Z = 3,
 }
 
-#[derive(Debug)]
-struct ParseInstructionError {}
-
-impl FromStr for RegisterLabel {
-    type Err = ParseInstructionError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "w" => Ok(Self::W),
-            "x" => Ok(Self::X),
-            "y" => Ok(Self::Y),
-            "z" => Ok(Self::Z),
-            _ => Err(Self::Err {}),
-        }
+fn parse_register(source: &Source, s: &str) -> Result<RegisterLabel, ParseError> {
+    match s {
+        "w" => Ok(RegisterLabel::W),
+        "x" => Ok(RegisterLabel::X),
+        "y" => Ok(RegisterLabel::Y),
+        "z" => Ok(RegisterLabel::Z),
+        _ => Err(source.error(s, format!("invalid register `{}`", s))),
     }
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Inp(RegisterLabel),
     Mul(RegisterLabel, RegisterLabel),
     // Instructions with immediate
@@ -62,55 +76,35 @@ enum Instruction {
     Eqli(RegisterLabel, Immediate),
 }
 
-impl FromStr for Instruction {
-    type Err = ParseInstructionError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let texts: Vec<_> = s.split(' ').collect();
-        let try_immediate = if texts.len() > 2 {
-            texts[2].parse::<Immediate>().ok()
-        } else {
-            None
-        };
-        let operand_0: RegisterLabel = texts[1].parse()?;
-        match texts[0] {
-            "inp" => Ok(Self::Inp(operand_0)),
-            "add" => {
-                if let Some(immediate) = try_immediate {
-                    Ok(Self::Addi(operand_0, immediate))
-                } else {
-                    Ok(Self::Add(operand_0, texts[2].parse()?))
-                }
-            }
-            "mul" => {
-                if let Some(immediate) = try_immediate {
-                    Ok(Self::Muli(operand_0, immediate))
-                } else {
-                    Ok(Self::Mul(operand_0, texts[2].parse()?))
-                }
-            }
-            "div" => {
-                if let Some(immediate) = try_immediate {
-                    Ok(Self::Divi(operand_0, immediate))
-                } else {
-                    unimplemented!();
-                }
-            }
-            "mod" => {
-                if let Some(immediate) = try_immediate {
-                    Ok(Self::Modi(operand_0, immediate))
-                } else {
-                    unimplemented!();
-                }
-            }
-            "eql" => {
-                if let Some(immediate) = try_immediate {
-                    Ok(Self::Eqli(operand_0, immediate))
-                } else {
-                    Ok(Self::Eql(operand_0, texts[2].parse()?))
-                }
+fn parse_instruction(source: &Source, s: &str) -> Result<Instruction, ParseError> {
+    let mut texts = s.splitn(4, ' ');
+    let operation = source.field(&mut texts, s, "operation")?;
+    let operand_0 = parse_register(source, source.field(&mut texts, s, "register")?)?;
+    let instruction = if operation == "inp" {
+        Instruction::Inp(operand_0)
+    } else {
+        let operand_1 = source.field(&mut texts, s, "operand")?;
+        let try_immediate = operand_1.parse::<Immediate>().ok();
+        let register = || parse_register(source, operand_1);
+        match (operation, try_immediate) {
+            ("add", Some(immediate)) => Instruction::Addi(operand_0, immediate),
+            ("add", None) => Instruction::Add(operand_0, register()?),
+            ("mul", Some(immediate)) => Instruction::Muli(operand_0, immediate),
+            ("mul", None) => Instruction::Mul(operand_0, register()?),
+            ("div" | "mod", Some(0)) => return Err(source.error(operand_1, "division by zero")),
+            ("div", Some(immediate)) => Instruction::Divi(operand_0, immediate),
+            ("mod", Some(immediate)) => Instruction::Modi(operand_0, immediate),
+            ("div" | "mod", None) => {
+                return Err(source.error(operand_1, "only immediate divisors are supported"))
             }
-            _ => Err(Self::Err {}),
+            ("eql", Some(immediate)) => Instruction::Eqli(operand_0, immediate),
+            ("eql", None) => Instruction::Eql(operand_0, register()?),
+            _ => return Err(source.error(operation, format!("invalid operation `{}`", operation))),
         }
+    };
+    match texts.next() {
+        Some(extra) => Err(source.error(extra, format!("unexpected operand `{}`", extra))),
+        None => Ok(instruction),
     }
 }
 
@@ -149,7 +143,7 @@ fn evaluate(registers: &mut [Immediate; 4], instructions: &[Instruction], queue:
 /// where a > 0 and b > 0. An increase transform may increases the degree of z on base 26.
 ///
 /// For example, here is an increase transform with parameter a = 11 and b = 14.
-/// ```
+/// ```text
 /// inp w
 /// mul x 0
 /// add x z
@@ -183,7 +177,7 @@ fn evaluate(registers: &mut [Immediate; 4], instructions: &[Instruction], queue:
 ///
 /// For example, here is a decrease transform with parameter a = -9 and b = 7
 ///
-/// ```
+/// ```text
 /// inp w
 /// mul x 0
 /// add x z
@@ -203,7 +197,8 @@ fn evaluate(registers: &mut [Immediate; 4], instructions: &[Instruction], queue:
 /// mul y x
 /// add z y
 /// ```
-///
+// The solver only needs b of increase transforms and a of decrease transforms
+#[allow(dead_code)]
 enum Transform {
     Increase(Immediate, Immediate),
     Decrease(Immediate, Immediate),
@@ -273,7 +268,7 @@ const DIGIT_LEN: usize = 14;
 const DIGIT_MAX: Immediate = 9;
 const DIGIT_MIN: Immediate = 1;
 
-fn p1(input: &[Instruction]) -> String {
+pub fn p1(input: &[Instruction]) -> String {
     let digit_constraints = get_digit_constraints(&extract_transform_paremeters(input));
     let mut digit = vec![0; DIGIT_LEN];
     for Constraint {
@@ -296,7 +291,7 @@ fn p1(input: &[Instruction]) -> String {
     digit.iter().map(|d| (*d as u8 + b'0') as char).collect()
 }
 
-fn p2(input: &[Instruction]) -> String {
+pub fn p2(input: &[Instruction]) -> String {
     let digit_constraints = get_digit_constraints(&extract_transform_paremeters(input));
     let mut digit = vec![0; DIGIT_LEN];
     for Constraint {
@@ -319,21 +314,24 @@ fn p2(input: &[Instruction]) -> String {
     digit.iter().map(|d| (*d as u8 + b'0') as char).collect()
 }
 
+aoc_core::solution!(Day24, 2021, 24, Vec<Instruction>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 24, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), "45989929946199");
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 24, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), "11912814611156");
     }
 }
@@ -1,19 +1,11 @@
-use std::cell::Cell;
+use aoc_core::ParseError;
+use aoc_grid::{Delta, Grid};
+use aoc_image::{Image, Recorder, Rgb, BLACK};
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day25.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    println!("Answer of p1: {}", p1(raw));
-    println!("Answer of p2: {}", p2());
-}
+pub type Input = Grid<char>;
 
-fn process(raw: &str) -> Vec<Vec<Cell<char>>> {
-    raw.lines().map(|n| n.chars().map(Cell::new).collect()).collect()
+pub fn process(raw: &str) -> Result<Input, ParseError> {
+    Grid::try_parse(raw, |ch| matches!(ch, VOID | EAST | DOWN).then_some(ch))
 }
 
 // Graph states:
@@ -21,75 +13,90 @@ const VOID: char = '.';
 const EAST: char = '>';
 const DOWN: char = 'v';
 
-/// Inplace sea cucumbers simulation
-fn simulate(graph: &[Vec<Cell<char>>]) -> bool {
-    let mut changed = false;
-    let width = graph[0].len();
-    let height = graph.len();
-    // east herds
-    #[allow(clippy::needless_range_loop)]
-    for y in 0..height {
-        if let Some(x_base) = graph[y].iter().position(|x| x.get() == VOID) {
-            let mut x = 0;
-            while x < width {
-                let cucumber = &graph[y][(x_base + x) % width];
-                if cucumber.get() == EAST {
-                    let next = &graph[y][(x_base + x + 1) % width];
-                    if next.get() == VOID {
-                        cucumber.swap(next);
-                        changed = true;
-                        x += 1;
-                    }
-                }
-                x += 1;
-            }
-        }
+/// Move every sea cucumber of `herd` whose next cell, `delta` away on the
+/// torus, was free before the herd moves
+fn move_herd(graph: &mut Input, herd: char, delta: Delta) -> bool {
+    let moves: Vec<_> = graph
+        .iter()
+        .filter(|&(_, &cucumber)| cucumber == herd)
+        .filter_map(|(pos, _)| Some((pos, graph.wrapping_offset(pos, delta)?)))
+        .filter(|&(_, next)| graph[next] == VOID)
+        .collect();
+    for &(pos, next) in &moves {
+        graph[pos] = VOID;
+        graph[next] = herd;
     }
-    // west herds
-    for x in 0..width {
-        if let Some(y_base) = graph.iter().position(|line| line[x].get() == VOID) {
-            let mut y = 0;
-            while y < height {
-                let cucumber = &graph[(y_base + y) % height][x];
-                if cucumber.get() == DOWN {
-                    let next = &graph[(y_base + y + 1) % height][x];
-                    if next.get() == VOID {
-                        cucumber.swap(next);
-                        changed = true;
-                        y += 1;
-                    }
-                }
-                y += 1;
-            }
-        }
-        
-    }
-    changed
+    !moves.is_empty()
+}
+
+/// Sea cucumbers simulation, returns whether any of them moved
+fn simulate(graph: &mut Input) -> bool {
+    let east_moved = move_herd(graph, EAST, (1, 0));
+    let down_moved = move_herd(graph, DOWN, (0, 1));
+    east_moved || down_moved
 }
 
-fn p1(raw: &str) -> usize {
-    let graph = process(raw);
+/// The herds once they stopped moving, and the step at which they did
+///
+/// `on_step` sees the initial state as step 0, then the state after each step.
+fn settle(input: &Input, mut on_step: impl FnMut(usize, &Input)) -> (Input, usize) {
+    let mut graph = input.clone();
     let mut step = 0;
+    on_step(step, &graph);
     loop {
         step += 1;
-        if !simulate(&graph) {
+        let moved = simulate(&mut graph);
+        on_step(step, &graph);
+        if !moved {
             break;
         }
     }
-    step
+    (graph, step)
+}
+
+pub fn p1(input: &Input) -> usize {
+    settle(input, |_, _| {}).1
 }
 
-fn p2() -> &'static str {
+pub fn p2(_input: &Input) -> &'static str {
     "Happy Holiday"
 }
 
+/// The settled herds, east-facing ones in orange and south-facing ones in blue
+pub fn render(input: &Input) -> Image {
+    settle(input, |_, _| {}).0.to_image(cucumber_color)
+}
+
+/// Record the herds until they stop moving
+pub fn animate(input: &Input, recorder: &mut Recorder) {
+    settle(input, |step, graph| {
+        recorder.record(
+            step,
+            || graph.render(|&cucumber| cucumber),
+            || graph.to_image(cucumber_color),
+        )
+    });
+}
+
+fn cucumber_color(&cucumber: &char) -> Rgb {
+    match cucumber {
+        EAST => [255, 140, 0],
+        DOWN => [40, 120, 255],
+        _ => BLACK,
+    }
+}
+
+aoc_core::solution!(Day25, 2021, 25, Input, render, animate);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        assert_eq!(p1(&raw), 334);
+        let raw = aoc_input::read(2021, 25, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 334);
     }
 }
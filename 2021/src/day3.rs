@@ -1,21 +1,38 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day3.txt"
-    };
-}
+use aoc_core::{ParseError, Source};
 
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+/// The widest numbers whose rates multiply without overflow
+const MAX_WIDTH: usize = 32;
 
-fn process(raw: &str) -> Vec<Vec<char>> {
-    raw.lines().map(|n| n.chars().collect()).collect()
+pub fn process(raw: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let source = Source::new(raw);
+    let mut width = None;
+    let result: Vec<_> = raw
+        .lines()
+        .map(|n| {
+            if let Some(i) = n.find(|c| c != '0' && c != '1') {
+                return Err(source.unexpected(&n[i..]));
+            }
+            match width {
+                Some(width) if width != n.len() => {
+                    Err(source.error(n, format!("{} bits instead of {}", n.len(), width)))
+                }
+                None if n.is_empty() || n.len() > MAX_WIDTH => {
+                    Err(source.error(n, format!("{} bits instead of 1 to {}", n.len(), MAX_WIDTH)))
+                }
+                _ => {
+                    width = Some(n.len());
+                    Ok(n.chars().collect())
+                }
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    if result.is_empty() {
+        return Err(source.error(raw, "no diagnostic number"));
+    }
+    Ok(result)
 }
 
-fn p1(input: &[Vec<char>]) -> usize {
+pub fn p1(input: &[Vec<char>]) -> usize {
     let height = input.len();
     let width = input[0].len();
     let mut mcb: String = "".to_string();
@@ -51,32 +68,35 @@ fn get_nested_common_bits(input: &[Vec<char>], mcb: bool) -> usize {
             bit = filtered[0][i]
         }
         bits.push(bit);
-        filtered = filtered.into_iter().filter(|x| x[i] == bit).collect();
+        filtered.retain(|x| x[i] == bit);
     }
     usize::from_str_radix(&bits, 2).unwrap()
 }
-fn p2(input: &[Vec<char>]) -> usize {
+pub fn p2(input: &[Vec<char>]) -> usize {
     let oxygen_generator_rating = get_nested_common_bits(input, true);
     let co2_scrubber_rating = get_nested_common_bits(input, false);
 
     oxygen_generator_rating * co2_scrubber_rating
 }
 
+aoc_core::solution!(Day3, 2021, 3, Vec<Vec<char>>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 3, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 741950);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 3, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 903810);
     }
 }
@@ -1,98 +1,67 @@
-use std::cell::Cell;
+use aoc_core::{ParseError, Source};
 use std::collections::HashSet;
 
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day4.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let state = new_state();
-    let input = process(raw, &state);
-    println!("Answer of p1: {}", p1(&input, &state));
-    println!("Answer of p2: {}", p2(&input, &state));
-}
-
 const WIDTH: usize = 5;
 const HEIGHT: usize = 5;
 const SIZE: usize = WIDTH * HEIGHT;
 const MOVE_MAX: usize = 100;
 
-type StateElement = (usize, Cell<bool>);
-
-type State = Vec<StateElement>;
+/// Whether a number has been drawn
+type Marks = [bool; MOVE_MAX];
 
-type Board<'a> = [&'a StateElement; SIZE];
+type Board = [usize; SIZE];
 
 #[derive(Debug)]
-struct Game<'a> {
+pub struct Game {
     moves: Vec<usize>,
-    boards: Vec<Board<'a>>,
+    boards: Vec<Board>,
 }
-fn process<'a>(raw: &str, state: &'a [StateElement]) -> Game<'a> {
+
+fn parse_number(source: &Source, text: &str) -> Result<usize, ParseError> {
+    match source.parse(text, "number")? {
+        n if n < MOVE_MAX => Ok(n),
+        n => Err(source.error(text, format!("{} is not below {}", n, MOVE_MAX))),
+    }
+}
+
+pub fn process(raw: &str) -> Result<Game, ParseError> {
+    let source = Source::new(raw);
     let mut line_iter = raw.split("\n\n");
-    let moves: Vec<_> = line_iter
-        .next()
-        .unwrap()
+    let moves = source
+        .field(&mut line_iter, raw, "moves")?
+        .trim_end()
         .split(',')
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect();
+        .map(|x| parse_number(&source, x))
+        .collect::<Result<_, _>>()?;
     let mut boards = vec![];
-    for board_text in line_iter {
-        let numbers: Vec<_> = board_text
-            .split_whitespace()
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect();
-        let mut board = [&(state[0]); WIDTH * HEIGHT];
-        for i in 0..SIZE {
-            board[i] = &(state[numbers[i]]);
+    for board_text in line_iter.filter(|text| !text.trim().is_empty()) {
+        let numbers: Vec<_> = board_text.split_whitespace().collect();
+        if numbers.len() != SIZE {
+            return Err(source.error(
+                board_text,
+                format!("board has {} numbers instead of {}", numbers.len(), SIZE),
+            ));
+        }
+        let mut board = [0; SIZE];
+        for (cell, n) in board.iter_mut().zip(numbers) {
+            *cell = parse_number(&source, n)?;
         }
         boards.push(board);
     }
-    Game { moves, boards }
-}
-
-fn new_state() -> State {
-    let mut state = vec![];
-    for i in 0..MOVE_MAX {
-        state.push((i, Cell::new(false)));
-    }
-    state
-}
-
-fn reset_state(state: &[StateElement]) {
-    for s in state {
-        s.1.set(false);
-    }
+    Ok(Game { moves, boards })
 }
 
-fn has_win(board: &Board) -> bool {
+fn has_win(board: &Board, marks: &Marks) -> bool {
     // check columns
     for i in 0..WIDTH {
-        let mut won = true;
-        for j in 0..HEIGHT {
-            won = won && board[j * WIDTH + i].1.get();
-            if !won {
-                break;
-            }
-        }
-        if won {
+        if (0..HEIGHT).all(|j| marks[board[j * WIDTH + i]]) {
             return true;
         }
     }
 
     // check rows
     for j in 0..HEIGHT {
-        let mut won = true;
-        for i in 0..WIDTH {
-            won = won && board[j * WIDTH + i].1.get();
-            if !won {
-                break;
-            }
-        }
-        if won {
+        if (0..WIDTH).all(|i| marks[board[j * WIDTH + i]]) {
             return true;
         }
     }
@@ -100,32 +69,32 @@ fn has_win(board: &Board) -> bool {
     false
 }
 
-fn sum_unmarked(board: &Board) -> usize {
-    board.iter().filter_map(|x| (!x.1.get()).then(|| x.0)).sum()
+fn sum_unmarked(board: &Board, marks: &Marks) -> usize {
+    board.iter().filter(|&&x| !marks[x]).sum()
 }
-fn p1(input: &Game, state: &[StateElement]) -> usize {
-    let boards = &input.boards;
-    for op in &input.moves {
-        state[*op].1.set(true);
-        for board in boards {
-            if has_win(board) {
-                return sum_unmarked(board) * op;
+
+pub fn p1(input: &Game) -> usize {
+    let mut marks = [false; MOVE_MAX];
+    for &op in &input.moves {
+        marks[op] = true;
+        for board in &input.boards {
+            if has_win(board, &marks) {
+                return sum_unmarked(board, &marks) * op;
             }
         }
     }
     unreachable!();
 }
 
-fn p2(input: &Game, state: &[StateElement]) -> usize {
-    reset_state(state);
-    let boards = &input.boards;
+pub fn p2(input: &Game) -> usize {
+    let mut marks = [false; MOVE_MAX];
     let mut won = HashSet::<usize>::new();
     let mut last_won_score: usize = 0;
-    for op in &input.moves {
-        state[*op].1.set(true);
-        for (i, board) in boards.iter().enumerate() {
-            if !won.contains(&i) && has_win(board) {
-                last_won_score = sum_unmarked(board) * op;
+    for &op in &input.moves {
+        marks[op] = true;
+        for (i, board) in input.boards.iter().enumerate() {
+            if !won.contains(&i) && has_win(board, &marks) {
+                last_won_score = sum_unmarked(board, &marks) * op;
                 won.insert(i);
             }
         }
@@ -133,23 +102,24 @@ fn p2(input: &Game, state: &[StateElement]) -> usize {
     last_won_score
 }
 
+aoc_core::solution!(Day4, 2021, 4, Game);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let state = new_state();
-        let input = process(&raw, &state);
-        assert_eq!(p1(&input, &state), 6592);
+        let raw = aoc_input::read(2021, 4, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p1(&input), 6592);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let state = new_state();
-        let input = process(&raw, &state);
-        assert_eq!(p2(&input, &state), 31755);
+        let raw = aoc_input::read(2021, 4, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
+        assert_eq!(p2(&input), 31755);
     }
 }
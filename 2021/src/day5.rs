@@ -1,18 +1,4 @@
-use std::num::ParseIntError;
-use std::str::FromStr;
-
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day5.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
 const HEIGHT: usize = 1000;
 const WIDTH: usize = 1000;
@@ -20,31 +6,39 @@ const WIDTH: usize = 1000;
 type Point = (usize, usize);
 
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     start: Point,
     end: Point,
 }
 
-impl FromStr for Line {
-    type Err = ParseIntError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start_text, end_text) = s.split_once(" -> ").unwrap();
-        let (x_s, y_s) = start_text.split_once(',').unwrap();
-        let (x_e, y_e) = end_text.split_once(',').unwrap();
-        let mut start = (x_s.parse()?, y_s.parse()?);
-        let mut end = (x_e.parse()?, y_e.parse()?);
-        // invariant: start.0 <= end.0 || start.1 <= end.1
-        let temp = start;
-        if start.0 > end.0 || start.1 > end.1 {
-            start = end;
-            end = temp;
-        }
-        Ok(Self { start, end })
+fn parse_point(source: &Source, s: &str) -> Result<Point, ParseError> {
+    let (x, y) = source.split_once(s, ",")?;
+    let (x, y) = (source.parse(x, "x")?, source.parse(y, "y")?);
+    if x >= WIDTH || y >= HEIGHT {
+        return Err(source.error(s, format!("{},{} is outside of the map", x, y)));
     }
+    Ok((x, y))
 }
 
-fn process(raw: &str) -> Vec<Line> {
-    raw.lines().map(|line| line.parse().unwrap()).collect()
+fn parse_line(source: &Source, s: &str) -> Result<Line, ParseError> {
+    let (start_text, end_text) = source.split_once(s, " -> ")?;
+    let mut start = parse_point(source, start_text)?;
+    let mut end = parse_point(source, end_text)?;
+    if start.0 != end.0 && start.1 != end.1 && start.0.abs_diff(end.0) != start.1.abs_diff(end.1) {
+        return Err(source.error(s, "the line is not horizontal, vertical nor diagonal"));
+    }
+    // invariant: start.0 <= end.0 || start.1 <= end.1
+    let temp = start;
+    if start.0 > end.0 || start.1 > end.1 {
+        start = end;
+        end = temp;
+    }
+    Ok(Line { start, end })
+}
+
+pub fn process(raw: &str) -> Result<Vec<Line>, ParseError> {
+    let source = Source::new(raw);
+    raw.lines().map(|line| parse_line(&source, line)).collect()
 }
 
 fn draw_and_sum(input: &[Line], consider_horizontal_vertical_only: bool) -> u32 {
@@ -90,29 +84,32 @@ fn draw_and_sum(input: &[Line], consider_horizontal_vertical_only: bool) -> u32
     sum
 }
 
-fn p1(input: &[Line]) -> u32 {
+pub fn p1(input: &[Line]) -> u32 {
     draw_and_sum(input, true)
 }
 
-fn p2(input: &[Line]) -> u32 {
+pub fn p2(input: &[Line]) -> u32 {
     draw_and_sum(input, false)
 }
 
+aoc_core::solution!(Day5, 2021, 5, Vec<Line>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 5, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 5774);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 5, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 18423);
     }
 }
@@ -1,24 +1,17 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day6.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
-fn process(raw: &str) -> [usize; 9] {
+pub fn process(raw: &str) -> Result<[usize; 9], ParseError> {
+    let source = Source::new(raw);
     let mut frequency = [0; 9];
-    for i in raw.chars() {
-        if let '0'..='6' = i {
-            frequency[i as usize - b'0' as usize] += 1
+    for timer in raw.trim_end().split(',') {
+        match timer {
+            "0" | "1" | "2" | "3" | "4" | "5" | "6" => {
+                frequency[source.parse::<usize>(timer, "timer")?] += 1
+            }
+            _ => return Err(source.error(timer, format!("invalid timer `{}`", timer))),
         }
     }
-    frequency
+    Ok(frequency)
 }
 
 fn count_fish(x: &[usize; 9], days: isize) -> [usize; 9] {
@@ -32,7 +25,8 @@ fn count_fish(x: &[usize; 9], days: isize) -> [usize; 9] {
     }
 }
 
-fn sum_fish_count(input: &[usize; 9], days: isize) -> usize {
+/// Count the lanternfish after the given days
+pub fn sum_fish_count(input: &[usize; 9], days: isize) -> usize {
     let mut sum = 0;
     for v in count_fish(input, days) {
         sum += v;
@@ -40,29 +34,71 @@ fn sum_fish_count(input: &[usize; 9], days: isize) -> usize {
     sum
 }
 
-fn p1(input: &[usize; 9]) -> usize {
+pub fn p1(input: &[usize; 9]) -> usize {
     sum_fish_count(input, 80)
 }
 
-fn p2(input: &[usize; 9]) -> usize {
+pub fn p2(input: &[usize; 9]) -> usize {
     sum_fish_count(input, 256)
 }
 
+aoc_core::solution!(Day6, 2021, 6, [usize; 9]);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
+    use aoc_rand::Rng;
+
+    /// Every fish on its own, a new one appended each time a timer wraps
+    fn naive(timers: &[usize], days: usize) -> usize {
+        let mut fish = timers.to_vec();
+        for _ in 0..days {
+            for i in 0..fish.len() {
+                if fish[i] == 0 {
+                    fish[i] = 6;
+                    fish.push(8);
+                } else {
+                    fish[i] -= 1;
+                }
+            }
+        }
+        fish.len()
+    }
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 6, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 360761);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 6, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 1632779838045);
     }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = Rng::new(6);
+        for _ in 0..200 {
+            let timers: Vec<_> = (0..1 + rng.below(10)).map(|_| rng.below(7)).collect();
+            let days = rng.below(60);
+            let raw = timers
+                .iter()
+                .map(|timer| timer.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let input = process(&raw).unwrap();
+            assert_eq!(
+                sum_fish_count(&input, days as isize),
+                naive(&timers, days),
+                "{} after {} days",
+                raw,
+                days
+            );
+        }
+    }
 }
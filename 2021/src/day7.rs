@@ -1,28 +1,22 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day7.txt"
-    };
-}
+use aoc_core::{ParseError, Source};
 
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+pub type Input = usize;
 
-type Input = usize;
-
-fn process(raw: &str) -> Vec<Input> {
-    let mut result: Vec<_> = raw.split(',').map(|i| i.parse().unwrap()).collect();
+pub fn process(raw: &str) -> Result<Vec<Input>, ParseError> {
+    let source = Source::new(raw);
+    let mut result = raw
+        .trim_end()
+        .split(',')
+        .map(|i| source.parse(i, "position"))
+        .collect::<Result<Vec<_>, _>>()?;
     result.sort_unstable();
-    result
+    Ok(result)
 }
 
-fn p1(input: &[Input]) -> usize {
+pub fn p1(input: &[Input]) -> usize {
     let median = {
         let len = input.len();
-        if len % 2 == 0 {
+        if len.is_multiple_of(2) {
             (input[len / 2] + input[len / 2 - 1]) / 2
         } else {
             input[len / 2]
@@ -45,7 +39,7 @@ fn distance_sum(input: &[Input], mean: usize) -> usize {
         .sum()
 }
 
-fn p2(input: &[Input]) -> usize {
+pub fn p2(input: &[Input]) -> usize {
     let ceiling_mean = {
         let len = input.len();
         let sum: usize = input.iter().sum();
@@ -57,21 +51,24 @@ fn p2(input: &[Input]) -> usize {
     )
 }
 
+aoc_core::solution!(Day7, 2021, 7, Vec<Input>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 7, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 328262);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 7, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 90040997);
     }
 }
@@ -1,25 +1,14 @@
-macro_rules! DATA_PATH {
-    () => {
-        "../data/day8.txt"
-    };
-}
-
-fn main() {
-    let raw = include_str!(DATA_PATH!());
-    let input = process(raw);
-    println!("Answer of p1: {}", p1(&input));
-    println!("Answer of p2: {}", p2(&input));
-}
+use aoc_core::{ParseError, Source};
 
 const SEGMENT_SIZE: usize = 7;
 
 #[derive(Debug)]
-struct Observation<'a> {
+pub struct Observation<'a> {
     signals: Vec<&'a str>,
     outputs: Vec<&'a str>,
 }
 
-type Input<'a> = Observation<'a>;
+pub type Input<'a> = Observation<'a>;
 
 // A transform is a map from (offset to 'a') to a one-segment mask.usize
 // for example, if a transform t maps 'a' to 'e', 'e' to 'a'
@@ -28,19 +17,44 @@ type Input<'a> = Observation<'a>;
 
 type Transform = Vec<u8>;
 
-fn process(raw: &str) -> Vec<Input> {
+/// Read `count` patterns of segments `a` to `g`
+fn parse_patterns<'a>(
+    source: &Source,
+    text: &'a str,
+    count: usize,
+) -> Result<Vec<&'a str>, ParseError> {
+    let patterns: Vec<_> = text.split(' ').collect();
+    if patterns.len() != count {
+        return Err(source.error(
+            text,
+            format!("{} patterns instead of {}", patterns.len(), count),
+        ));
+    }
+    for pattern in &patterns {
+        if let Some(i) = pattern.find(|c| !('a'..='g').contains(&c)) {
+            return Err(source.unexpected(&pattern[i..]));
+        }
+        if pattern.is_empty() || pattern.len() > SEGMENT_SIZE {
+            return Err(source.error(pattern, format!("invalid pattern `{}`", pattern)));
+        }
+    }
+    Ok(patterns)
+}
+
+pub fn process(raw: &str) -> Result<Vec<Input<'_>>, ParseError> {
+    let source = Source::new(raw);
     raw.lines()
         .map(|line| {
-            let (signals_text, outputs_text) = line.split_once(" | ").unwrap();
-            Observation {
-                signals: signals_text.split(' ').collect(),
-                outputs: outputs_text.split(' ').collect(),
-            }
+            let (signals_text, outputs_text) = source.split_once(line, " | ")?;
+            Ok(Observation {
+                signals: parse_patterns(&source, signals_text, 10)?,
+                outputs: parse_patterns(&source, outputs_text, 4)?,
+            })
         })
         .collect()
 }
 
-fn p1(input: &[Input]) -> usize {
+pub fn p1(input: &[Input]) -> usize {
     let mut sum = 0;
     for observation in input {
         sum += observation
@@ -69,7 +83,6 @@ fn decode_bits(input: u8) -> usize {
 }
 
 /// Pack a signal to bits.
-
 fn pack_signal_to_bits(input: &str) -> u8 {
     let mut activated_segments = 0x0;
     for s in input.chars() {
@@ -192,7 +205,7 @@ fn compute_inverse_transform(signals: &[&str]) -> Transform {
     transform
 }
 
-fn p2(input: &[Input]) -> usize {
+pub fn p2(input: &[Input]) -> usize {
     let mut result = 0;
     for observation in input {
         let transform = compute_inverse_transform(&observation.signals);
@@ -201,9 +214,12 @@ fn p2(input: &[Input]) -> usize {
     result
 }
 
+aoc_core::solution!(Day8, 2021, 8, Vec<Input<'a>>);
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_input::Variant;
     #[test]
     fn test_pack_signal_to_bits() {
         assert_eq!(pack_signal_to_bits("cf"), 0b0100100);
@@ -231,15 +247,15 @@ mod test {
 
     #[test]
     fn test_p1() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 8, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p1(&input), 310);
     }
 
     #[test]
     fn test_p2() {
-        let raw = include_str!(DATA_PATH!());
-        let input = process(raw);
+        let raw = aoc_input::read(2021, 8, Variant::Input).unwrap();
+        let input = process(&raw).unwrap();
         assert_eq!(p2(&input), 915941);
     }
 }
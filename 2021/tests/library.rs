@@ -0,0 +1,48 @@
+use a2021::{day15, day18, day22, day6};
+use aoc_input::Variant;
+
+#[test]
+fn test_shortest_path() {
+    let raw = aoc_input::read(2021, 15, Variant::Example).unwrap();
+    let input = day15::process(&raw).unwrap();
+    assert_eq!(
+        day15::shortest_path_from_top_left_to_bottom_right(&input, &1),
+        40
+    );
+    assert_eq!(
+        day15::shortest_path_from_top_left_to_bottom_right(&input, &5),
+        315
+    );
+}
+
+#[test]
+fn test_snailfish_add() {
+    let sum = day18::add(
+        &day18::parse_sn("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap(),
+        &day18::parse_sn("[1,1]").unwrap(),
+    );
+    assert_eq!(
+        sum,
+        day18::parse_sn("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap()
+    );
+    assert_eq!(day18::magnitude(&sum), 1384);
+}
+
+#[test]
+fn test_cube_intersect() {
+    let cube = day22::Cube::new([10, 10, 10], [12, 12, 12]);
+    let other = day22::parse_cube("x=11..13,y=11..13,z=11..13").unwrap();
+    let intersection = cube.intersect(&other).unwrap();
+    assert_eq!(intersection, day22::Cube::new([11, 11, 11], [12, 12, 12]));
+    assert_eq!(intersection.volume(), 8);
+
+    let apart = day22::Cube::new([13, 0, 0], [14, 12, 12]);
+    assert_eq!(cube.intersect(&apart), None);
+}
+
+#[test]
+fn test_lanternfish() {
+    let input = day6::process("3,4,3,1,2").unwrap();
+    assert_eq!(day6::sum_fish_count(&input, 18), 26);
+    assert_eq!(day6::sum_fish_count(&input, 80), 5934);
+}
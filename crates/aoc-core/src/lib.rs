@@ -0,0 +1,8 @@
+mod parse;
+mod registry;
+mod solution;
+
+pub use aoc_image::{Image, Recorder};
+pub use parse::{ParseError, Source};
+pub use registry::{Animator, Answer, Registry, Renderer, Report, Runner};
+pub use solution::{Solution, Unsolved};
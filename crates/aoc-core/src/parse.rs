@@ -0,0 +1,199 @@
+//! Errors pointing at the offending place of a puzzle input
+//!
+//! Parsers keep slicing the raw input, so any fragment they fail on can be
+//! located by its address inside the input through a [`Source`].
+
+use std::fmt::{self, Display};
+use std::io;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(err: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// The raw input being parsed, to report errors at their position in it
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    raw: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(raw: &'a str) -> Self {
+        Self { raw }
+    }
+
+    /// An error at the start of `fragment`, which must be a slice of the input
+    pub fn error(&self, fragment: &str, message: impl Into<String>) -> ParseError {
+        let start = self.raw.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + fragment.len() <= self.raw.len())
+            .expect("the fragment is not a slice of the input");
+        let before = &self.raw[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            message,
+        )
+    }
+
+    /// An error right after `fragment`, for something missing at its end
+    pub fn error_after(&self, fragment: &str, message: impl Into<String>) -> ParseError {
+        self.error(&fragment[fragment.len()..], message)
+    }
+
+    /// Move an error found by parsing `fragment` on its own to its place in
+    /// the input
+    pub fn relocate(&self, fragment: &str, err: ParseError) -> ParseError {
+        let start = self.error(fragment, String::new());
+        let column = if err.line == 1 {
+            start.column + err.column - 1
+        } else {
+            err.column
+        };
+        ParseError::new(start.line + err.line - 1, column, err.message)
+    }
+
+    /// An error on the first character of `fragment`
+    pub fn unexpected(&self, fragment: &str) -> ParseError {
+        match fragment.chars().next() {
+            Some(c) => self.error(fragment, format!("unexpected `{}`", c)),
+            None => self.error(fragment, "unexpected end of input"),
+        }
+    }
+
+    /// Parse the whole `fragment`, naming it `what` on failure
+    pub fn parse<T: FromStr>(&self, fragment: &str, what: &str) -> Result<T, ParseError> {
+        fragment
+            .parse()
+            .map_err(|_| self.error(fragment, format!("invalid {} `{}`", what, fragment)))
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        separator: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(separator)
+            .ok_or_else(|| self.error_after(s, format!("missing `{}`", separator.escape_debug())))
+    }
+
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("expected `{}`", prefix)))
+    }
+
+    /// The next item of `items`, the fields of `s`, or an error at its end
+    pub fn field(
+        &self,
+        items: &mut impl Iterator<Item = &'a str>,
+        s: &'a str,
+        what: &str,
+    ) -> Result<&'a str, ParseError> {
+        items
+            .next()
+            .ok_or_else(|| self.error_after(s, format!("missing {}", what)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RAW: &str = "12,7\n3,x\n\né,5";
+
+    #[test]
+    fn test_location() {
+        let source = Source::new(RAW);
+        let at = |error: ParseError| (error.line, error.column);
+        let lines: Vec<_> = RAW.lines().collect();
+        assert_eq!(at(source.error(RAW, "")), (1, 1));
+        assert_eq!(at(source.error(&lines[1][2..], "")), (2, 3));
+        assert_eq!(at(source.error(lines[2], "")), (3, 1));
+        // columns count characters rather than bytes
+        assert_eq!(at(source.error(&lines[3][2..], "")), (4, 2));
+        assert_eq!(at(source.error_after(RAW, "")), (4, 4));
+    }
+
+    #[test]
+    fn test_relocate() {
+        let source = Source::new(RAW);
+        let fragment = &RAW[2..];
+        let error = Source::new(fragment).error(&fragment[1..], "");
+        assert_eq!(
+            source.relocate(fragment, error.clone()),
+            source.error(&RAW[3..], "")
+        );
+        let error = Source::new(fragment).error(&fragment[5..], "");
+        assert_eq!(
+            source.relocate(fragment, error),
+            source.error(&RAW[7..], "")
+        );
+    }
+
+    #[test]
+    fn test_helpers() {
+        let source = Source::new(RAW);
+        let line = RAW.lines().nth(1).unwrap();
+        let (x, y) = source.split_once(line, ",").unwrap();
+        assert_eq!(source.parse::<u8>(x, "x"), Ok(3));
+        assert_eq!(
+            source.parse::<u8>(y, "y").unwrap_err(),
+            ParseError::new(2, 3, "invalid y `x`")
+        );
+        assert_eq!(
+            source.split_once(line, "-").unwrap_err(),
+            ParseError::new(2, 4, "missing `-`")
+        );
+        assert_eq!(
+            source.strip_prefix(line, "4").unwrap_err(),
+            ParseError::new(2, 1, "expected `4`")
+        );
+        assert_eq!(source.unexpected(y).message, "unexpected `x`");
+        let mut fields = line.split(',').skip(2);
+        assert_eq!(
+            source.field(&mut fields, line, "z").unwrap_err(),
+            ParseError::new(2, 4, "missing z")
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_foreign_fragment() {
+        Source::new(RAW).error(&String::from("12"), "");
+    }
+}
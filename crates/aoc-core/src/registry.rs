@@ -0,0 +1,191 @@
+use crate::{Image, ParseError, Recorder, Solution};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+pub struct Answer {
+    pub part: u8,
+    pub value: String,
+    /// Whether the part gave an actual answer rather than [`crate::Unsolved`]
+    pub solved: bool,
+    pub elapsed: Duration,
+}
+
+pub struct Report {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// A type-erased solution: parse the raw input, then solve the given parts
+pub type Runner = fn(raw: &str, parts: &[u8]) -> Result<Report, ParseError>;
+
+/// A type-erased [`Solution::render`]
+pub type Renderer = fn(raw: &str) -> Result<Option<Image>, ParseError>;
+
+/// A type-erased [`Solution::animate`]
+pub type Animator = fn(raw: &str, recorder: &mut Recorder) -> Result<bool, ParseError>;
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn run<S: Solution>(raw: &str, parts: &[u8]) -> Result<Report, ParseError> {
+    let (input, parse) = timed(|| S::parse(raw));
+    let input = input?;
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let solved = match part {
+                1 => S::PART1,
+                2 => S::PART2,
+                _ => panic!("part {} does not exist", part),
+            };
+            let (value, elapsed) = timed(|| match part {
+                1 => S::part1(&input).to_string(),
+                _ => S::part2(&input).to_string(),
+            });
+            Answer {
+                part,
+                value,
+                solved,
+                elapsed,
+            }
+        })
+        .collect();
+    Ok(Report { parse, answers })
+}
+
+fn render<S: Solution>(raw: &str) -> Result<Option<Image>, ParseError> {
+    Ok(S::render(&S::parse(raw)?))
+}
+
+fn animate<S: Solution>(raw: &str, recorder: &mut Recorder) -> Result<bool, ParseError> {
+    Ok(S::animate(&S::parse(raw)?, recorder))
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+    runner: Runner,
+    renderer: Renderer,
+    animator: Animator,
+}
+
+/// Every known solution, keyed by (year, day)
+#[derive(Default)]
+pub struct Registry {
+    entries: BTreeMap<(u16, u8), Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<S: Solution>(&mut self) {
+        let entry = Entry {
+            runner: run::<S>,
+            renderer: render::<S>,
+            animator: animate::<S>,
+        };
+        let previous = self.entries.insert((S::YEAR, S::DAY), entry);
+        assert!(
+            previous.is_none(),
+            "{} day {} is registered twice",
+            S::YEAR,
+            S::DAY
+        );
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<Runner> {
+        self.entries.get(&(year, day)).map(|entry| entry.runner)
+    }
+
+    pub fn renderer(&self, year: u16, day: u8) -> Option<Renderer> {
+        self.entries.get(&(year, day)).map(|entry| entry.renderer)
+    }
+
+    pub fn animator(&self, year: u16, day: u8) -> Option<Animator> {
+        self.entries.get(&(year, day)).map(|entry| entry.animator)
+    }
+
+    /// Iterate over ((year, day), runner) in chronological order
+    pub fn iter(&self) -> impl Iterator<Item = ((u16, u8), Runner)> + '_ {
+        self.entries.iter().map(|(&key, entry)| (key, entry.runner))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Source;
+    use std::fmt::Display;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 2;
+        const PART1: bool = true;
+        const PART2: bool = true;
+
+        type Input<'a> = Vec<u32>;
+
+        fn parse(raw: &str) -> Result<Self::Input<'_>, ParseError> {
+            let source = Source::new(raw);
+            raw.lines()
+                .map(|line| source.parse(line, "number"))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input<'_>) -> impl Display {
+            input.iter().sum::<u32>()
+        }
+
+        fn part2(input: &Self::Input<'_>) -> impl Display {
+            input.iter().max().unwrap().to_owned()
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register::<Sum>();
+        assert_eq!(registry.len(), 1);
+        assert!(registry.get(2000, 1).is_none());
+
+        let report = registry.get(2000, 2).unwrap()("1\n5\n3", &[2, 1]).unwrap();
+        let answers: Vec<_> = report
+            .answers
+            .iter()
+            .map(|answer| (answer.part, answer.value.as_str()))
+            .collect();
+        assert_eq!(answers, [(2, "5"), (1, "9")]);
+        assert!(report.answers.iter().all(|answer| answer.solved));
+        assert_eq!(registry.renderer(2000, 2).unwrap()("1\n5\n3"), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut registry = Registry::new();
+        registry.register::<Sum>();
+        let error = registry.get(2000, 2).unwrap()("1\nfive\n3", &[1]).err();
+        assert_eq!(error, Some(ParseError::new(2, 1, "invalid number `five`")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_register_twice() {
+        let mut registry = Registry::new();
+        registry.register::<Sum>();
+        registry.register::<Sum>();
+    }
+}
@@ -0,0 +1,245 @@
+use crate::ParseError;
+use aoc_image::{Image, Recorder};
+use std::fmt::{self, Display};
+
+/// A puzzle solution: parse the raw input once, then solve both parts from it
+///
+/// The parsed input may borrow from the raw input, e.g. `Vec<&str>`.
+/// Malformed inputs are reported rather than panicking. Puzzles about a
+/// picture can also draw it, see [`Solution::render`], and simulations can
+/// replay their steps, see [`Solution::animate`].
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// Whether [`Solution::part1`] gives an answer rather than [`Unsolved`]
+    const PART1: bool = false;
+    /// Whether [`Solution::part2`] gives an answer rather than [`Unsolved`]
+    const PART2: bool = false;
+
+    type Input<'a>;
+
+    fn parse(raw: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part1(_input: &Self::Input<'_>) -> impl Display {
+        Unsolved
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> impl Display {
+        Unsolved
+    }
+
+    /// An image of the final state of the puzzle, if it has one
+    fn render(_input: &Self::Input<'_>) -> Option<Image> {
+        None
+    }
+
+    /// Replay the simulation of the puzzle into `recorder`, returns whether
+    /// the puzzle has one
+    fn animate(_input: &Self::Input<'_>, _recorder: &mut Recorder) -> bool {
+        false
+    }
+}
+
+/// The answer of a part that has not been solved yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not solved")
+    }
+}
+
+/// Implement [`Solution`] on a new unit struct `$name`, delegating to the
+/// `process`, `p1` and `p2` functions in scope, `process` returning a
+/// `Result<$input, ParseError>`
+///
+/// Pass `p1` or `p2` after the input type when only that part is available. Pass
+/// `render` to draw the puzzle with the `render` function in scope, returning
+/// an `Image`, and `animate` to replay it with the `animate` function in
+/// scope, taking a `&mut Recorder`.
+#[macro_export]
+macro_rules! solution {
+    ($name:ident, $year:literal, $day:literal, $input:ty, p1 $(, $hook:ident)*) => {
+        $crate::solution!(@impl $name, $year, $day, $input, [p1 $($hook)*]);
+    };
+    ($name:ident, $year:literal, $day:literal, $input:ty, p2 $(, $hook:ident)*) => {
+        $crate::solution!(@impl $name, $year, $day, $input, [p2 $($hook)*]);
+    };
+    ($name:ident, $year:literal, $day:literal, $input:ty $(, $hook:ident)*) => {
+        $crate::solution!(@impl $name, $year, $day, $input, [p1 p2 $($hook)*]);
+    };
+    (@impl $name:ident, $year:literal, $day:literal, $input:ty, [$($hook:ident)*]) => {
+        pub struct $name;
+
+        impl $crate::Solution for $name {
+            const YEAR: u16 = $year;
+            const DAY: u8 = $day;
+
+            type Input<'a> = $input;
+
+            fn parse(raw: &str) -> Result<Self::Input<'_>, $crate::ParseError> {
+                process(raw)
+            }
+
+            $($crate::solution!(@hook $hook);)*
+        }
+    };
+    (@hook p1) => {
+        const PART1: bool = true;
+
+        fn part1(input: &Self::Input<'_>) -> impl ::std::fmt::Display {
+            p1(input)
+        }
+    };
+    (@hook p2) => {
+        const PART2: bool = true;
+
+        fn part2(input: &Self::Input<'_>) -> impl ::std::fmt::Display {
+            p2(input)
+        }
+    };
+    (@hook render) => {
+        fn render(input: &Self::Input<'_>) -> Option<$crate::Image> {
+            Some(render(input))
+        }
+    };
+    (@hook animate) => {
+        fn animate(input: &Self::Input<'_>, recorder: &mut $crate::Recorder) -> bool {
+            animate(input, recorder);
+            true
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Words;
+
+    impl Solution for Words {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+        const PART1: bool = true;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(raw: &str) -> Result<Self::Input<'_>, ParseError> {
+            Ok(raw.split_whitespace().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> impl Display {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Words::parse("lorem ipsum dolor").unwrap();
+        assert_eq!(Words::part1(&input).to_string(), "3");
+        assert_eq!(Words::part2(&input).to_string(), Unsolved.to_string());
+    }
+
+    mod longest {
+        fn process(raw: &str) -> Result<Vec<&str>, crate::ParseError> {
+            Ok(raw.split_whitespace().collect())
+        }
+
+        fn p2(input: &[&str]) -> usize {
+            input.iter().map(|word| word.len()).max().unwrap()
+        }
+
+        crate::solution!(Longest, 2000, 3, Vec<&'a str>, p2);
+    }
+
+    #[test]
+    fn test_solution_macro() {
+        use longest::Longest;
+        let input = Longest::parse("lorem ipsum dolores").unwrap();
+        assert_eq!(Longest::part1(&input).to_string(), Unsolved.to_string());
+        assert_eq!((Longest::PART1, Longest::PART2), (false, true));
+        assert_eq!(Longest::part2(&input).to_string(), "7");
+        assert_eq!((Longest::YEAR, Longest::DAY), (2000, 3));
+        assert!(Longest::render(&input).is_none());
+    }
+
+    mod bars {
+        use crate::Image;
+
+        fn process(raw: &str) -> Result<Vec<usize>, crate::ParseError> {
+            Ok(raw.bytes().map(|b| (b - b'0') as usize).collect())
+        }
+
+        fn p2(input: &[usize]) -> usize {
+            input.iter().sum()
+        }
+
+        fn render(input: &[usize]) -> Image {
+            let height = input.iter().max().copied().unwrap_or_default();
+            Image::from_fn(input.len(), height, |x, y| {
+                [if height - y <= input[x] { 255 } else { 0 }; 3]
+            })
+        }
+
+        crate::solution!(Bars, 2000, 4, Vec<usize>, p2, render);
+    }
+
+    #[test]
+    fn test_solution_macro_render() {
+        use bars::Bars;
+        let input = Bars::parse("132").unwrap();
+        assert_eq!(Bars::part2(&input).to_string(), "6");
+        let image = Bars::render(&input).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 3));
+        assert_eq!((image[(0, 1)], image[(1, 0)]), ([0; 3], [255; 3]));
+        let dir = std::env::temp_dir().join(format!("aoc-bars-{}", std::process::id()));
+        let mut recorder = crate::Recorder::new(&dir, 1).unwrap();
+        assert!(!Bars::animate(&input, &mut recorder));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    mod countdown {
+        use crate::Recorder;
+
+        fn process(raw: &str) -> Result<usize, crate::ParseError> {
+            Ok(raw.len())
+        }
+
+        fn p1(input: &usize) -> usize {
+            *input
+        }
+
+        fn p2(input: &usize) -> usize {
+            input * 2
+        }
+
+        fn animate(input: &usize, recorder: &mut Recorder) {
+            for step in 0..=*input {
+                let left = input - step;
+                recorder.record(step, || left.to_string(), || unreachable!());
+            }
+        }
+
+        crate::solution!(Countdown, 2000, 5, usize, animate);
+    }
+
+    #[test]
+    fn test_solution_macro_animate() {
+        use countdown::Countdown;
+        let input = Countdown::parse("abc").unwrap();
+        assert_eq!(Countdown::part1(&input).to_string(), "3");
+        assert_eq!(Countdown::part2(&input).to_string(), "6");
+        assert!(Countdown::render(&input).is_none());
+        let dir = std::env::temp_dir().join(format!("aoc-countdown-{}", std::process::id()));
+        let mut recorder = crate::Recorder::new(&dir, 2).unwrap();
+        assert!(Countdown::animate(&input, &mut recorder));
+        assert_eq!(recorder.finish().unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("000002.txt")).unwrap(),
+            "1"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
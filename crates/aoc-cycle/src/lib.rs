@@ -0,0 +1,174 @@
+//! Finding where a deterministic simulation starts repeating itself
+//!
+//! A simulation is an initial state and a step function. As soon as a state
+//! comes back, every following state repeats with the same period, so the
+//! state at any step can be found without simulating that far.
+//!
+//! [`brent`] only keeps a couple of states at a time, [`hashed`] keeps every
+//! state until the first repetition but steps through them only once.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States from step `start` on repeat every `length` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step which is part of the cycle
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as the one of step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The state after `n` steps, simulating at most `start + length` of them
+    pub fn state_at<S: Clone>(&self, initial: &S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        let mut state = initial.clone();
+        for _ in 0..self.reduce(n) {
+            state = step(&state);
+        }
+        state
+    }
+}
+
+/// Brent's cycle detection
+///
+/// Loops forever if the states never repeat.
+pub fn brent<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length by moving the hare ahead of the tortoise, teleporting
+    // the tortoise to the hare every power of two steps
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then the start, moving both `length` steps apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Cycle detection remembering the step of every state seen
+///
+/// Loops forever if the states never repeat.
+pub fn hashed<S: Clone + Hash + Eq>(initial: &S, step: impl FnMut(&S) -> S) -> Cycle {
+    History::new(initial, step).cycle
+}
+
+/// Every state until the first repetition, to look up the state at any step
+/// without simulating again
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S: Clone + Hash + Eq> History<S> {
+    /// Simulate until a state comes back
+    ///
+    /// Loops forever if the states never repeat.
+    pub fn new(initial: &S, mut step: impl FnMut(&S) -> S) -> Self {
+        let mut seen = HashMap::new();
+        let mut states = vec![initial.clone()];
+        loop {
+            let state = &states[states.len() - 1];
+            if let Some(start) = seen.insert(state.clone(), states.len() - 1) {
+                let length = states.len() - 1 - start;
+                states.pop();
+                return Self {
+                    cycle: Cycle { start, length },
+                    states,
+                };
+            }
+            let next = step(state);
+            states.push(next);
+        }
+    }
+
+    /// The state after `n` steps
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A rho-shaped sequence: 0..7 then 7..12 over and over
+    fn step(&state: &u32) -> u32 {
+        if state == 11 {
+            7
+        } else {
+            state + 1
+        }
+    }
+
+    /// x² + 1 modulo some number, cycling after a tail
+    fn square(&state: &u64) -> u64 {
+        (state * state + 1) % 10_007
+    }
+
+    fn cycle(start: usize, length: usize) -> Cycle {
+        Cycle { start, length }
+    }
+
+    fn naive<S: Clone>(initial: &S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        let mut state = initial.clone();
+        for _ in 0..n {
+            state = step(&state);
+        }
+        state
+    }
+
+    #[test]
+    fn test_detectors() {
+        let expected = cycle(7, 5);
+        assert_eq!(brent(&0, step), expected);
+        assert_eq!(hashed(&0, step), expected);
+        assert_eq!(brent(&9, step), cycle(0, 5));
+        assert_eq!(brent(&3u64, square), hashed(&3u64, square));
+        // a fixed point is a cycle of length 1
+        assert_eq!(brent(&4, |&x| x), cycle(0, 1));
+        assert_eq!(hashed(&4, |&x| x), cycle(0, 1));
+    }
+
+    #[test]
+    fn test_state_at() {
+        let found = brent(&3u64, square);
+        let history = History::new(&3u64, square);
+        for n in [0, 1, found.start, found.start + found.length, 12_345] {
+            let expected = naive(&3u64, square, n);
+            assert_eq!(found.state_at(&3u64, square, n), expected);
+            assert_eq!(*history.state_at(n), expected);
+        }
+        assert_eq!(
+            cycle(7, 5).reduce(1_000_000_000),
+            7 + (1_000_000_000 - 7) % 5
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Fuzzing the input parsers, which must report malformed inputs rather than
+//! panic
+//!
+//! There is one target per parser: the `process` function of every solution
+//! known to the runner, and the parsers of single lines which are public.
+//! Inputs are mutated from the puzzle inputs and examples found on disk, so
+//! most of them are almost valid and go deep into the parsers.
+
+mod mutate;
+
+use aoc_input::{Resolver, Variant};
+use aoc_rand::Rng;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Mutants which parse are kept to be mutated further, up to this many
+/// besides the seeds
+const CORPUS_SIZE: usize = 256;
+
+/// Held while the panic hook is swapped, by one fuzzing run at a time
+static HOOK: Mutex<()> = Mutex::new(());
+
+pub struct Target {
+    pub name: String,
+    /// Whether the input is valid
+    parse: Box<dyn Fn(&str) -> bool>,
+    seeds: Vec<String>,
+}
+
+impl Target {
+    fn new(name: String, parse: impl Fn(&str) -> bool + 'static, seeds: Vec<String>) -> Self {
+        Self {
+            name,
+            parse: Box::new(parse),
+            seeds,
+        }
+    }
+}
+
+/// Every target, seeded with the inputs and examples under `resolver`
+pub fn targets(resolver: &Resolver) -> Vec<Target> {
+    let inputs = |year, day| -> Vec<String> {
+        [Variant::Input, Variant::Example]
+            .into_iter()
+            .filter_map(|variant| resolver.read(year, day, variant).ok())
+            .collect()
+    };
+    let lines = |year, day| -> Vec<String> {
+        inputs(year, day)
+            .iter()
+            .flat_map(|raw| raw.lines().map(str::to_string))
+            .collect()
+    };
+
+    let mut targets: Vec<_> = aoc::solutions::registry()
+        .iter()
+        .map(|((year, day), runner)| {
+            // solving no part only parses
+            let parse = move |raw: &str| runner(raw, &[]).is_ok();
+            Target::new(format!("{}-day{}", year, day), parse, inputs(year, day))
+        })
+        .collect();
+    targets.push(Target::new(
+        "2021-day18-snailfish".to_string(),
+        |raw| a2021::day18::parse_sn(raw).is_ok(),
+        lines(2021, 18),
+    ));
+    targets.push(Target::new(
+        "2021-day22-cuboid".to_string(),
+        |raw| a2021::day22::parse_cube(raw).is_ok(),
+        lines(2021, 22)
+            .iter()
+            .filter_map(|line| line.split_once(' ').map(|(_, cube)| cube.to_string()))
+            .collect(),
+    ));
+    targets
+}
+
+/// An input the target panicked on
+#[derive(Debug)]
+pub struct Crash {
+    pub input: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Stats {
+    pub runs: usize,
+    /// How many inputs parsed without error
+    pub parsed: usize,
+    pub slowest: Duration,
+}
+
+/// Parse `runs` mutants of the seeds of `target`, no longer than `max_len`
+/// bytes unless a seed is, stopping at the first panic
+pub fn fuzz(target: &Target, seed: u64, runs: usize, max_len: usize) -> Result<Stats, Crash> {
+    let mut rng = Rng::new(seed);
+    let mut corpus = target.seeds.clone();
+    if corpus.is_empty() {
+        corpus.push(String::new());
+    }
+    let seeds = corpus.len();
+    let mut stats = Stats::default();
+
+    // the panics are reported as crashes, not printed
+    let guard = HOOK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut crash = None;
+    for run in 0..runs {
+        // every seed as it is first
+        let input = match corpus.get(run).filter(|_| run < seeds) {
+            Some(seed) => seed.clone(),
+            None => mutate::mutate(&mut rng, &corpus, max_len),
+        };
+        let start = Instant::now();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| (target.parse)(&input)));
+        stats.slowest = stats.slowest.max(start.elapsed());
+        stats.runs += 1;
+        match outcome {
+            Ok(false) => {}
+            Ok(true) => {
+                stats.parsed += 1;
+                if run < seeds {
+                    // already in the corpus
+                } else if corpus.len() < seeds + CORPUS_SIZE {
+                    corpus.push(input);
+                } else {
+                    corpus[seeds + rng.below(CORPUS_SIZE)] = input;
+                }
+            }
+            Err(payload) => {
+                crash = Some(Crash {
+                    input,
+                    message: message(payload.as_ref()),
+                });
+                break;
+            }
+        }
+    }
+    panic::set_hook(hook);
+    drop(guard);
+    match crash {
+        Some(crash) => Err(crash),
+        None => Ok(stats),
+    }
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_crash() {
+        let target = Target::new(
+            "brackets".to_string(),
+            |raw| {
+                let depth = raw.chars().filter(|&c| c == '[').count();
+                assert!(depth < 3, "too deep");
+                true
+            },
+            vec!["[1,2]".to_string()],
+        );
+        let crash = fuzz(&target, 3, 10_000, 64).unwrap_err();
+        assert_eq!(crash.message, "too deep");
+        assert!(crash.input.matches('[').count() >= 3);
+    }
+
+    /// A short run of every target, `aoc-fuzz` runs longer ones
+    #[test]
+    fn test_targets() {
+        let targets = targets(&Resolver::from_env());
+        assert!(targets.len() > aoc::solutions::registry().len());
+        for target in &targets {
+            if let Err(crash) = fuzz(target, 0, 300, 1024) {
+                panic!(
+                    "{} panicked on {:?}: {}",
+                    target.name, crash.input, crash.message
+                );
+            }
+        }
+    }
+}
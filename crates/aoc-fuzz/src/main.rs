@@ -0,0 +1,104 @@
+use aoc_fuzz::{fuzz, targets};
+use aoc_input::Resolver;
+use std::env;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "\
+Usage: aoc-fuzz [TARGET...] [--runs N] [--seed SEED] [--max-len N] [--artifacts DIR]
+       aoc-fuzz --list
+
+Parses N mutated inputs (10000 by default) with every TARGET, or every target,
+stopping at the first panic. The input is then saved to
+DIR/TARGET/crash-HASH (DIR being fuzz/artifacts by default), to be replayed
+with `aoc run --input`. Mutants are at most N bytes long (4096 by default)
+unless the input they come from is longer.";
+
+const DEFAULT_RUNS: usize = 10_000;
+const DEFAULT_MAX_LEN: usize = 4096;
+const DEFAULT_SEED: u64 = 1;
+const DEFAULT_ARTIFACTS: &str = "fuzz/artifacts";
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut names = vec![];
+    let mut runs = DEFAULT_RUNS;
+    let mut seed = DEFAULT_SEED;
+    let mut max_len = DEFAULT_MAX_LEN;
+    let mut artifacts = DEFAULT_ARTIFACTS.to_string();
+    let mut list = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--list" {
+            list = true;
+            continue;
+        }
+        if !arg.starts_with("--") {
+            names.push(arg.clone());
+            continue;
+        }
+        let value = iter
+            .next()
+            .unwrap_or_else(|| fail(&format!("missing value for `{}`", arg)));
+        let invalid = || format!("invalid value `{}` for `{}`", value, arg);
+        match arg.as_str() {
+            "--runs" => runs = value.parse().unwrap_or_else(|_| fail(&invalid())),
+            "--seed" => seed = value.parse().unwrap_or_else(|_| fail(&invalid())),
+            "--max-len" => max_len = value.parse().unwrap_or_else(|_| fail(&invalid())),
+            "--artifacts" => artifacts = value.clone(),
+            _ => fail(&format!("unknown flag `{}`", arg)),
+        }
+    }
+
+    let targets = targets(&Resolver::from_env());
+    if list {
+        for target in &targets {
+            println!("{}", target.name);
+        }
+        return;
+    }
+    if let Some(name) = names
+        .iter()
+        .find(|name| !targets.iter().any(|t| &t.name == *name))
+    {
+        fail(&format!("unknown target `{}`, see --list", name));
+    }
+
+    let mut crashed = false;
+    for target in &targets {
+        if !names.is_empty() && !names.contains(&target.name) {
+            continue;
+        }
+        match fuzz(target, seed, runs, max_len) {
+            Ok(stats) => println!(
+                "{:<22} {} runs, {} parsed, slowest {:.2}ms",
+                target.name,
+                stats.runs,
+                stats.parsed,
+                stats.slowest.as_secs_f64() * 1000.0
+            ),
+            Err(crash) => {
+                crashed = true;
+                let mut hasher = DefaultHasher::new();
+                crash.input.hash(&mut hasher);
+                let dir = Path::new(&artifacts).join(&target.name);
+                let path = dir.join(format!("crash-{:016x}", hasher.finish()));
+                println!("{:<22} panicked: {}", target.name, crash.message);
+                match fs::create_dir_all(&dir).and_then(|_| fs::write(&path, &crash.input)) {
+                    Ok(()) => println!("{:<22} input saved to {}", "", path.display()),
+                    Err(err) => eprintln!("error: cannot write {}: {}", path.display(), err),
+                }
+            }
+        }
+    }
+    if crashed {
+        process::exit(1);
+    }
+}
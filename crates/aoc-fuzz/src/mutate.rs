@@ -0,0 +1,102 @@
+use aoc_rand::Rng;
+
+/// Fragments of the puzzle formats, to build almost valid inputs
+const TOKENS: [&str; 24] = [
+    "\n",
+    "\n\n",
+    " ",
+    ",",
+    "-",
+    "..",
+    "->",
+    " -> ",
+    ":",
+    "=",
+    "[",
+    "]",
+    "(",
+    ")",
+    "<",
+    ">",
+    "#",
+    ".",
+    "0",
+    "9",
+    "-1",
+    "255",
+    "99999999999999999999",
+    "é",
+];
+
+/// A random input derived from one of `corpus`, shortened to `max_len` bytes
+/// when it grows longer than that and its origin
+pub fn mutate(rng: &mut Rng, corpus: &[String], max_len: usize) -> String {
+    let origin = rng.pick(corpus);
+    let mut bytes = origin.as_bytes().to_vec();
+    for _ in 0..1 + rng.below(4) {
+        mutation(rng, corpus, &mut bytes);
+    }
+    bytes.truncate(max_len.max(origin.len()));
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn mutation(rng: &mut Rng, corpus: &[String], bytes: &mut Vec<u8>) {
+    let at = rng.below(bytes.len() + 1);
+    match rng.below(7) {
+        // flip a bit
+        0 if at < bytes.len() => bytes[at] ^= 1 << rng.below(8),
+        // overwrite with a token
+        1 if at < bytes.len() => {
+            let token = rng.pick(&TOKENS).as_bytes();
+            let end = (at + token.len()).min(bytes.len());
+            bytes.splice(at..end, token.iter().copied());
+        }
+        // remove a range
+        2 if at < bytes.len() => {
+            let end = at + 1 + rng.below((bytes.len() - at).min(64));
+            bytes.drain(at..end);
+        }
+        // repeat a range
+        3 if at < bytes.len() => {
+            let end = at + 1 + rng.below((bytes.len() - at).min(64));
+            let range = bytes[at..end].to_vec();
+            for _ in 0..1 + rng.below(8) {
+                bytes.splice(at..at, range.iter().copied());
+            }
+        }
+        // cut the end off
+        4 => bytes.truncate(at),
+        // graft a piece of another input
+        5 => {
+            let other = rng.pick(corpus).as_bytes();
+            let start = rng.below(other.len() + 1);
+            let end = start + rng.below(other.len() - start + 1).min(256);
+            bytes.splice(at..at, other[start..end].iter().copied());
+        }
+        // insert a token
+        _ => {
+            let token = rng.pick(&TOKENS).as_bytes();
+            bytes.splice(at..at, token.iter().copied());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mutate() {
+        let corpus = vec!["on x=10..12,y=10..12,z=10..12".to_string()];
+        let mut rng = Rng::new(50);
+        let mutants: Vec<_> = (0..100).map(|_| mutate(&mut rng, &corpus, 40)).collect();
+        assert!(mutants.iter().all(|mutant| mutant.len() <= 40 * 3));
+        assert!(
+            mutants
+                .iter()
+                .filter(|&mutant| mutant != &corpus[0])
+                .count()
+                > 90
+        );
+    }
+}
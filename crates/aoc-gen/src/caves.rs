@@ -0,0 +1,80 @@
+use aoc_rand::Rng;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+/// Caves are numbered with a byte, start, end and the big caves included
+pub const SIZES: RangeInclusive<usize> = 1..=200;
+
+/// A cave system with `size` small caves besides start and end, and a big
+/// cave for every four small ones
+///
+/// Every cave is reachable from start, and big caves only lead to small
+/// caves, otherwise paths could go back and forth between them forever.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let small: Vec<_> = (0..size).map(|i| label(i, b'a')).collect();
+    let big: Vec<_> = (0..size.div_ceil(4)).map(|i| label(i, b'A')).collect();
+    let mut edges = BTreeSet::new();
+    let mut connect = |a: &str, b: &str| {
+        if a != b && !edges.contains(&(b.to_string(), a.to_string())) {
+            edges.insert((a.to_string(), b.to_string()));
+        }
+    };
+
+    // each small cave hangs from start or an earlier one, maybe through a
+    // big cave
+    for (i, cave) in small.iter().enumerate() {
+        let parent = match rng.below(i + 1) {
+            0 => "start",
+            j => &small[j - 1],
+        };
+        if rng.chance(1, 3) {
+            let hub = rng.pick(&big).as_str();
+            connect(parent, hub);
+            connect(hub, cave);
+        } else {
+            connect(parent, cave);
+        }
+    }
+    // a few more passages, then the way out
+    for _ in 0..size / 2 {
+        connect(rng.pick(&small).as_str(), rng.pick(&small).as_str());
+    }
+    for _ in 0..1 + rng.below(2) {
+        connect(rng.pick(&small).as_str(), "end");
+    }
+
+    let mut lines: Vec<_> = edges
+        .into_iter()
+        .map(|(a, b)| format!("{}-{}", a, b))
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+/// Two letters from `first`, as in the puzzle inputs
+fn label(i: usize, first: u8) -> String {
+    [first + (i / 26) as u8, first + (i % 26) as u8]
+        .iter()
+        .map(|&b| char::from(b))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let caves = generate(&mut Rng::new(12), 20);
+        let mut big_neighbors = 0;
+        for line in caves.lines() {
+            let (a, b) = line.split_once('-').unwrap();
+            assert_ne!(a, b);
+            let big = |cave: &str| cave.starts_with(|c: char| c.is_ascii_uppercase());
+            assert!(!(big(a) && big(b)), "{}", line);
+            big_neighbors += big(a) as usize + big(b) as usize;
+        }
+        assert!(big_neighbors > 0);
+        assert!(caves.contains("start") && caves.contains("end"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Random puzzle inputs, larger than the official ones, to find the limits
+//! of the solutions
+//!
+//! Every generator is seeded, so a seed and a size always give the same
+//! input, and writes the puzzle format the `process` function of its day
+//! accepts.
+
+mod caves;
+mod passports;
+mod reboot;
+mod risk_map;
+mod snailfish;
+
+use aoc_rand::Rng;
+use std::ops::RangeInclusive;
+
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+    /// What the size counts
+    pub unit: &'static str,
+    /// About the size of the official inputs
+    pub default_size: usize,
+    pub sizes: RangeInclusive<usize>,
+    write: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    /// The input of the given size drawn from `seed`
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        assert!(
+            self.sizes.contains(&size),
+            "the size must be in {:?}",
+            self.sizes
+        );
+        (self.write)(&mut Rng::new(seed), size)
+    }
+}
+
+/// Every generator, by year and day
+pub const GENERATORS: [Generator; 5] = [
+    Generator {
+        year: 2020,
+        day: 4,
+        unit: "passports",
+        default_size: 300,
+        sizes: 1..=1_000_000,
+        write: passports::generate,
+    },
+    Generator {
+        year: 2021,
+        day: 12,
+        unit: "small caves",
+        default_size: 10,
+        sizes: caves::SIZES,
+        write: caves::generate,
+    },
+    Generator {
+        year: 2021,
+        day: 15,
+        unit: "cells per side",
+        default_size: 100,
+        sizes: 1..=10_000,
+        write: risk_map::generate,
+    },
+    Generator {
+        year: 2021,
+        day: 18,
+        unit: "numbers",
+        default_size: 100,
+        sizes: 1..=1_000_000,
+        write: snailfish::generate,
+    },
+    Generator {
+        year: 2021,
+        day: 22,
+        unit: "steps",
+        default_size: 420,
+        sizes: 1..=1_000_000,
+        write: reboot::generate,
+    },
+];
+
+pub fn generator(year: u16, day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.year == year && g.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        for generator in &GENERATORS {
+            let size = *generator.sizes.start() + 5;
+            let input = generator.generate(49, size);
+            assert_eq!(generator.generate(49, size), input);
+            assert_ne!(generator.generate(50, size), input);
+        }
+        assert!(generator(2021, 15).is_some());
+        assert!(generator(2021, 16).is_none());
+    }
+}
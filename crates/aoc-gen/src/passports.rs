@@ -0,0 +1,90 @@
+use aoc_rand::Rng;
+
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// `size` passports separated by blank lines, their fields in any order and
+/// split over lines
+///
+/// Most passports are valid, the others miss a field or have an invalid
+/// value.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut passports = vec![];
+    for _ in 0..size {
+        let mut fields = vec![];
+        for key in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"] {
+            if rng.chance(19, 20) {
+                fields.push(format!("{}:{}", key, value(rng, key)));
+            }
+        }
+        if rng.chance(1, 2) || fields.is_empty() {
+            fields.push(format!("cid:{}", rng.range(1..=350)));
+        }
+        rng.shuffle(&mut fields);
+        let mut passport = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.chance(1, 3) { '\n' } else { ' ' });
+            }
+            passport.push_str(field);
+        }
+        passports.push(passport);
+    }
+    passports.join("\n\n") + "\n"
+}
+
+/// A value for `key`, invalid once in ten
+fn value(rng: &mut Rng, key: &str) -> String {
+    let valid = rng.chance(9, 10);
+    match key {
+        "byr" if valid => rng.range(1920..=2002).to_string(),
+        "byr" => rng.range(2003..=2030).to_string(),
+        "iyr" if valid => rng.range(2010..=2020).to_string(),
+        "iyr" => rng.range(1990..=2009).to_string(),
+        "eyr" if valid => rng.range(2020..=2030).to_string(),
+        "eyr" => rng.range(2031..=2040).to_string(),
+        "hgt" if valid && rng.chance(1, 2) => format!("{}cm", rng.range(150..=193)),
+        "hgt" if valid => format!("{}in", rng.range(59..=76)),
+        "hgt" => match rng.below(3) {
+            0 => format!("{}cm", rng.range(194..=250)),
+            1 => format!("{}in", rng.range(20..=58)),
+            // no unit
+            _ => rng.range(59..=193).to_string(),
+        },
+        "hcl" => {
+            let digits: String = (0..6)
+                .map(|_| char::from_digit(rng.below(16) as u32, 16).unwrap())
+                .collect();
+            if valid {
+                format!("#{}", digits)
+            } else {
+                digits
+            }
+        }
+        "ecl" if valid => rng.pick(&EYE_COLORS).to_string(),
+        "ecl" => rng.pick(&["xry", "zzz", "utc", "gmt"]).to_string(),
+        "pid" => {
+            let length = if valid { 9 } else { *rng.pick(&[8, 10]) };
+            (0..length).map(|_| rng.below(10).to_string()).collect()
+        }
+        _ => unreachable!("unknown field `{}`", key),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let passports = generate(&mut Rng::new(4), 30);
+        let passports: Vec<_> = passports.trim_end().split("\n\n").collect();
+        assert_eq!(passports.len(), 30);
+        for passport in passports {
+            for field in passport.split_whitespace() {
+                let (key, value) = field.split_once(':').unwrap();
+                assert!(["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"].contains(&key));
+                assert!(!value.is_empty());
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use aoc_rand::Rng;
+use std::fmt::Write;
+
+/// The initialization region of part 1
+const REGION: i64 = 50;
+/// Cuboids outside of it are spread like the ones of the puzzle inputs
+const SPREAD: i64 = 100_000;
+
+/// `size` reboot steps, the first twentieth within the initialization region
+/// as in the puzzle inputs
+///
+/// The first step turns cubes on, a quarter of the others turn them off.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let initialization = (size / 20).max(1);
+    let mut steps = String::new();
+    for i in 0..size {
+        let on = i == 0 || rng.chance(3, 4);
+        let (bound, extents) = if i < initialization {
+            (REGION, 5..=30)
+        } else {
+            (SPREAD, 5_000..=30_000)
+        };
+        steps.push_str(if on { "on " } else { "off " });
+        for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+            let extent = rng.range(extents.clone());
+            let start = rng.range(-bound..=bound - extent);
+            let separator = if axis == 0 { "" } else { "," };
+            write!(steps, "{}{}={}..{}", separator, name, start, start + extent).unwrap();
+        }
+        steps.push('\n');
+    }
+    steps
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let steps = generate(&mut Rng::new(22), 40);
+        assert_eq!(steps.lines().count(), 40);
+        assert!(steps.starts_with("on x="));
+        // the two first steps are in the initialization region
+        for line in steps.lines().take(2) {
+            let numbers = line
+                .split(|c: char| !c.is_ascii_digit() && c != '-')
+                .filter_map(|number| number.parse::<i64>().ok());
+            assert!(numbers.into_iter().all(|n| n.abs() <= REGION), "{}", line);
+        }
+    }
+}
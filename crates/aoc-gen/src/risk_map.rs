@@ -0,0 +1,26 @@
+use aoc_rand::Rng;
+
+/// A square map of `size` by `size` risks from 1 to 9
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut map = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            map.push(char::from(b'1' + rng.below(9) as u8));
+        }
+        map.push('\n');
+    }
+    map
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let map = generate(&mut Rng::new(15), 7);
+        assert_eq!(map.lines().count(), 7);
+        assert!(map.lines().all(|line| line.len() == 7));
+        assert!(!map.contains('0'));
+    }
+}
@@ -0,0 +1,52 @@
+use aoc_rand::Rng;
+
+/// Reduced numbers have no pair nested inside four pairs
+const MAX_DEPTH: usize = 4;
+
+/// `size` reduced snailfish numbers, one per line
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = String::new();
+    for _ in 0..size {
+        element(rng, 0, &mut numbers);
+        numbers.push('\n');
+    }
+    numbers
+}
+
+/// Write an element inside `depth` pairs, deeper ones being more likely to
+/// be regular numbers
+fn element(rng: &mut Rng, depth: usize, out: &mut String) {
+    if depth == MAX_DEPTH || (depth > 0 && rng.chance(depth, MAX_DEPTH + 1)) {
+        out.push(char::from(b'0' + rng.below(10) as u8));
+    } else {
+        out.push('[');
+        element(rng, depth + 1, out);
+        out.push(',');
+        element(rng, depth + 1, out);
+        out.push(']');
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let numbers = generate(&mut Rng::new(18), 50);
+        assert_eq!(numbers.lines().count(), 50);
+        for line in numbers.lines() {
+            assert!(line.starts_with('['));
+            let mut depth = 0;
+            for c in line.chars() {
+                match c {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    _ => {}
+                }
+                assert!(depth <= MAX_DEPTH, "{}", line);
+            }
+            assert_eq!(depth, 0);
+        }
+    }
+}
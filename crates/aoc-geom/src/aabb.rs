@@ -0,0 +1,139 @@
+use crate::{Coord, Point};
+use std::ops::RangeInclusive;
+
+/// An axis-aligned box including both corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb<const N: usize> {
+    min: Point<N>,
+    max: Point<N>,
+}
+
+impl<const N: usize> Aabb<N> {
+    /// Panics when `min` exceeds `max` on some axis
+    pub fn new(min: impl Into<Point<N>>, max: impl Into<Point<N>>) -> Self {
+        let (min, max) = (min.into(), max.into());
+        assert!(
+            (0..N).all(|i| min[i] <= max[i]),
+            "{:?} is not below {:?}",
+            min,
+            max
+        );
+        Self { min, max }
+    }
+
+    /// The smallest box containing every point, if there is any
+    pub fn bounding(points: impl IntoIterator<Item = Point<N>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        Some(Self { min, max })
+    }
+
+    pub fn min(&self) -> Point<N> {
+        self.min
+    }
+
+    pub fn max(&self) -> Point<N> {
+        self.max
+    }
+
+    pub fn range(&self, axis: usize) -> RangeInclusive<Coord> {
+        self.min[axis]..=self.max[axis]
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (0..N)
+            .all(|i| min[i] <= max[i])
+            .then_some(Self { min, max })
+    }
+
+    /// Number of integer points inside the box
+    pub fn volume(&self) -> u64 {
+        (0..N)
+            .map(|i| (self.max[i] - self.min[i]) as u64 + 1)
+            .product()
+    }
+
+    /// Number of integer points on each axis
+    pub fn size(&self) -> Point<N> {
+        self.max - self.min + Point([1; N])
+    }
+
+    /// Every integer point, the last axis varying fastest
+    pub fn points(&self) -> impl Iterator<Item = Point<N>> + '_ {
+        let mut next = Some(self.min);
+        std::iter::from_fn(move || {
+            let current = next?;
+            let mut point = current;
+            next = (0..N).rev().find_map(|i| {
+                if point[i] < self.max[i] {
+                    point[i] += 1;
+                    Some(point)
+                } else {
+                    point[i] = self.min[i];
+                    None
+                }
+            });
+            Some(current)
+        })
+    }
+}
+
+impl<const N: usize> From<(Point<N>, Point<N>)> for Aabb<N> {
+    fn from((min, max): (Point<N>, Point<N>)) -> Self {
+        Self::new(min, max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        let cube = Aabb::new([10, 10, 10], [12, 12, 12]);
+        let other = Aabb::new([11, 11, 11], [13, 13, 13]);
+        let intersection = cube.intersect(&other).unwrap();
+        assert_eq!(intersection, Aabb::new([11, 11, 11], [12, 12, 12]));
+        assert_eq!(intersection.volume(), 8);
+        assert_eq!(cube.volume(), 27);
+        assert_eq!(cube.intersect(&Aabb::new([13, 0, 0], [14, 12, 12])), None);
+        // touching boxes share a face
+        let face = cube
+            .intersect(&Aabb::new([12, 0, 0], [20, 20, 20]))
+            .unwrap();
+        assert_eq!(face.volume(), 9);
+    }
+
+    #[test]
+    fn test_points() {
+        let square = Aabb::new([-1, 0], [0, 2]);
+        let points: Vec<_> = square.points().collect();
+        assert_eq!(points.len() as u64, square.volume());
+        assert_eq!(points[0], Point::new([-1, 0]));
+        assert_eq!(points[1], Point::new([-1, 1]));
+        assert!(points.iter().all(|p| square.contains(p)));
+        assert!(!square.contains(&Point::new([1, 0])));
+        assert_eq!(square.size(), Point::new([2, 3]));
+        assert_eq!(square.range(1), 0..=2);
+    }
+
+    #[test]
+    fn test_bounding() {
+        let points = [Point::new([3, -1]), Point::new([0, 4]), Point::new([1, 1])];
+        assert_eq!(Aabb::bounding(points), Some(Aabb::new([0, -1], [3, 4])));
+        assert_eq!(Aabb::<2>::bounding([]), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverted() {
+        Aabb::new([1], [0]);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{range_set::span, Aabb, Coord, Point, RangeSet};
+
+/// A set of integer points in N dimensions, built by adding and taking out
+/// boxes
+///
+/// The space is cut into slabs along the first axis, each slab holding the
+/// set of its points on the remaining axes, down to a [`RangeSet`] on the
+/// last axis. Volumes are exact however the boxes overlap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoxSet<const N: usize> {
+    root: Layer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Layer {
+    /// The last axis
+    Ranges(RangeSet),
+    /// Disjoint slabs `start..=end` in increasing order, never empty and
+    /// merged with their neighbor when they hold the same set
+    Slabs(Vec<(Coord, Coord, Layer)>),
+}
+
+impl Layer {
+    fn empty(axes: usize) -> Self {
+        if axes == 1 {
+            Self::Ranges(RangeSet::new())
+        } else {
+            Self::Slabs(vec![])
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Ranges(set) => set.is_empty(),
+            Self::Slabs(slabs) => slabs.is_empty(),
+        }
+    }
+
+    /// Add or take out the box from `min` to `max`, on the axes of the layer
+    fn apply(&mut self, min: &[Coord], max: &[Coord], insert: bool) {
+        let slabs = match self {
+            Self::Ranges(set) if insert => return set.insert(min[0]..=max[0]),
+            Self::Ranges(set) => return set.remove(min[0]..=max[0]),
+            Self::Slabs(slabs) => slabs,
+        };
+        let (start, end) = (min[0], max[0]);
+        let mut pieces = vec![];
+        let mut uncovered = RangeSet::new();
+        uncovered.insert(start..=end);
+        for (a, b, layer) in slabs.drain(..) {
+            if b < start || a > end {
+                pieces.push((a, b, layer));
+                continue;
+            }
+            if a < start {
+                pieces.push((a, start - 1, layer.clone()));
+            }
+            if b > end {
+                pieces.push((end + 1, b, layer.clone()));
+            }
+            let (a, b) = (a.max(start), b.min(end));
+            uncovered.remove(a..=b);
+            let mut inside = layer;
+            inside.apply(&min[1..], &max[1..], insert);
+            pieces.push((a, b, inside));
+        }
+        if insert {
+            for range in uncovered.ranges() {
+                let mut inside = Self::empty(min.len() - 1);
+                inside.apply(&min[1..], &max[1..], insert);
+                pieces.push((*range.start(), *range.end(), inside));
+            }
+        }
+        pieces.sort_unstable_by_key(|&(a, _, _)| a);
+
+        for (a, b, layer) in pieces {
+            if layer.is_empty() {
+                continue;
+            }
+            match slabs.last_mut() {
+                Some((_, last_end, last))
+                    if last_end.checked_add(1) == Some(a) && *last == layer =>
+                {
+                    *last_end = b
+                }
+                _ => slabs.push((a, b, layer)),
+            }
+        }
+    }
+
+    fn contains(&self, point: &[Coord]) -> bool {
+        match self {
+            Self::Ranges(set) => set.contains(point[0]),
+            Self::Slabs(slabs) => {
+                let index = slabs.partition_point(|&(_, b, _)| b < point[0]);
+                slabs
+                    .get(index)
+                    .is_some_and(|(a, _, layer)| *a <= point[0] && layer.contains(&point[1..]))
+            }
+        }
+    }
+
+    fn volume(&self) -> u64 {
+        match self {
+            Self::Ranges(set) => set.len(),
+            Self::Slabs(slabs) => slabs.iter().fold(0, |volume, (a, b, layer)| {
+                volume.saturating_add(span(*a, *b).saturating_mul(layer.volume()))
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Default for BoxSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> BoxSet<N> {
+    pub fn new() -> Self {
+        assert!(N > 0, "points need at least one axis");
+        Self {
+            root: Layer::empty(N),
+        }
+    }
+
+    /// Add every point of `aabb`
+    pub fn insert(&mut self, aabb: &Aabb<N>) {
+        self.root
+            .apply(aabb.min().coords(), aabb.max().coords(), true);
+    }
+
+    /// Take out every point of `aabb`
+    pub fn remove(&mut self, aabb: &Aabb<N>) {
+        self.root
+            .apply(aabb.min().coords(), aabb.max().coords(), false);
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        self.root.contains(point.coords())
+    }
+
+    /// The number of points in the set, saturating at `u64::MAX`
+    pub fn volume(&self) -> u64 {
+        self.root.volume()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = BoxSet::<3>::new();
+        set.insert(&Aabb::new([10, 10, 10], [12, 12, 12]));
+        set.insert(&Aabb::new([11, 11, 11], [13, 13, 13]));
+        assert_eq!(set.volume(), 27 + 19);
+        set.remove(&Aabb::new([9, 9, 9], [11, 11, 11]));
+        assert_eq!(set.volume(), 27 + 19 - 8);
+        set.insert(&Aabb::new([10, 10, 10], [10, 10, 10]));
+        assert_eq!(set.volume(), 39);
+        assert!(set.contains(&Point::new([10, 10, 10])));
+        assert!(!set.contains(&Point::new([11, 10, 10])));
+        assert!(set.contains(&Point::new([13, 13, 13])));
+        set.remove(&Aabb::new([0, 0, 0], [20, 20, 20]));
+        assert!(set.is_empty());
+        assert_eq!(set, BoxSet::new());
+    }
+
+    #[test]
+    fn test_against_points() {
+        // every point of a small space, toggled box by box
+        let boxes = [
+            (true, Aabb::new([0, 0], [5, 3])),
+            (true, Aabb::new([3, 2], [8, 8])),
+            (false, Aabb::new([1, 1], [4, 6])),
+            (true, Aabb::new([-2, 4], [2, 4])),
+            (false, Aabb::new([6, -1], [6, 9])),
+        ];
+        let mut set = BoxSet::<2>::new();
+        let space = Aabb::new([-3, -3], [10, 10]);
+        let mut points = vec![false; space.volume() as usize];
+        for (on, aabb) in boxes {
+            if on {
+                set.insert(&aabb);
+            } else {
+                set.remove(&aabb);
+            }
+            for (point, lit) in space.points().zip(points.iter_mut()) {
+                if aabb.contains(&point) {
+                    *lit = on;
+                }
+            }
+            for (point, &lit) in space.points().zip(&points) {
+                assert_eq!(set.contains(&point), lit, "{:?}", point);
+            }
+            let count = points.iter().filter(|&&lit| lit).count();
+            assert_eq!(set.volume(), count as u64);
+        }
+    }
+
+    #[test]
+    fn test_one_axis() {
+        let mut set = BoxSet::<1>::new();
+        set.insert(&Aabb::new([0], [9]));
+        set.remove(&Aabb::new([3], [4]));
+        assert_eq!(set.volume(), 8);
+    }
+
+    #[test]
+    fn test_extremes() {
+        let mut set = BoxSet::<2>::new();
+        set.insert(&Aabb::new([Coord::MIN, 0], [Coord::MAX, 0]));
+        // 2^64 points, one more than a u64 holds
+        assert_eq!(set.volume(), u64::MAX);
+        set.remove(&Aabb::new([Coord::MIN, 0], [Coord::MIN, 0]));
+        set.remove(&Aabb::new([Coord::MAX, 0], [Coord::MAX, 0]));
+        assert_eq!(set.volume(), u64::MAX - 1);
+        set.insert(&Aabb::new(
+            [Coord::MIN, Coord::MIN],
+            [Coord::MAX, Coord::MAX],
+        ));
+        assert!(set.contains(&Point::new([Coord::MAX, Coord::MIN])));
+        assert_eq!(set.volume(), u64::MAX);
+        set.remove(&Aabb::new(
+            [Coord::MIN, Coord::MIN],
+            [Coord::MAX, Coord::MAX],
+        ));
+        assert!(set.is_empty());
+    }
+}
@@ -0,0 +1,14 @@
+//! Points, vectors, norms and rotations in N dimensions, and sets of
+//! integer ranges and boxes
+
+mod aabb;
+mod box_set;
+mod point;
+mod range_set;
+mod rotation;
+
+pub use aabb::Aabb;
+pub use box_set::BoxSet;
+pub use point::{Coord, Point, Vector};
+pub use range_set::RangeSet;
+pub use rotation::{rotations, Rotation};
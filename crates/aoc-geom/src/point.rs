@@ -0,0 +1,178 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+pub type Coord = i64;
+
+/// A position, or a vector, with `N` integer coordinates
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize>(pub [Coord; N]);
+
+/// Points double as the offset between two points
+pub type Vector<const N: usize> = Point<N>;
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Self([0; N]);
+
+    pub fn new(coords: [Coord; N]) -> Self {
+        Self(coords)
+    }
+
+    pub fn coords(&self) -> &[Coord; N] {
+        &self.0
+    }
+
+    pub fn map(self, f: impl FnMut(Coord) -> Coord) -> Self {
+        Self(self.0.map(f))
+    }
+
+    /// Combine the coordinates of two points one axis at a time
+    pub fn zip_with(self, other: Self, mut f: impl FnMut(Coord, Coord) -> Coord) -> Self {
+        Self(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    pub fn dot(&self, other: &Self) -> Coord {
+        self.0.iter().zip(&other.0).map(|(a, b)| a * b).sum()
+    }
+
+    /// L1 norm
+    pub fn manhattan(&self) -> Coord {
+        self.0.iter().map(|x| x.abs()).sum()
+    }
+
+    /// L∞ norm
+    pub fn chebyshev(&self) -> Coord {
+        self.0.iter().map(|x| x.abs()).max().unwrap_or(0)
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> Coord {
+        (*self - *other).manhattan()
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> Coord {
+        (*self - *other).chebyshev()
+    }
+
+    /// The sign of every coordinate, i.e. one step towards each axis
+    pub fn signum(self) -> Self {
+        self.map(Coord::signum)
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        self.zip_with(other, Coord::min)
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        self.zip_with(other, Coord::max)
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> From<[Coord; N]> for Point<N> {
+    fn from(coords: [Coord; N]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = Coord;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x)
+    }
+}
+
+impl<const N: usize> Mul<Coord> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Coord) -> Self::Output {
+        self.map(|x| x * rhs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new([1, -2, 3]);
+        let b = Point::new([4, 0, -1]);
+        assert_eq!(a + b, Point::new([5, -2, 2]));
+        assert_eq!(a - b, Point::new([-3, -2, 4]));
+        assert_eq!(-a, Point::new([-1, 2, -3]));
+        assert_eq!(a * 2, Point::new([2, -4, 6]));
+        assert_eq!(a.dot(&b), 1);
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(a.min(b), Point::new([1, -2, -1]));
+        assert_eq!(b.signum(), Point::new([1, 0, -1]));
+    }
+
+    #[test]
+    fn test_norms() {
+        let a = Point::new([1, -5, 3]);
+        assert_eq!(a.manhattan(), 9);
+        assert_eq!(a.chebyshev(), 5);
+        assert_eq!(a.manhattan_distance(&Point::ORIGIN), 9);
+        assert_eq!(
+            Point::new([2, 2]).chebyshev_distance(&Point::new([5, 1])),
+            3
+        );
+        assert_eq!(Point::<0>::ORIGIN.chebyshev(), 0);
+    }
+}
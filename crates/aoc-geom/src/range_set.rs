@@ -0,0 +1,195 @@
+use crate::Coord;
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as sorted, disjoint inclusive ranges
+///
+/// Touching ranges are merged, so two sets holding the same integers are
+/// equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+    ranges: Vec<(Coord, Coord)>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every integer of `range`
+    pub fn insert(&mut self, range: RangeInclusive<Coord>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // ranges ending before `start - 1` and starting after `end + 1` stay
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e < start.saturating_sub(1));
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| s <= end.saturating_add(1));
+        let merged = self.ranges[first..last]
+            .iter()
+            .fold((start, end), |(s, e), &(a, b)| (s.min(a), e.max(b)));
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Take out every integer of `range`
+    pub fn remove(&mut self, range: RangeInclusive<Coord>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        let mut kept = vec![];
+        if let Some(&(s, _)) = self.ranges[first..last].first() {
+            if s < start {
+                kept.push((s, start - 1));
+            }
+        }
+        if let Some(&(_, e)) = self.ranges[first..last].last() {
+            if e > end {
+                kept.push((end + 1, e));
+            }
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: Coord) -> bool {
+        let index = self.ranges.partition_point(|&(_, e)| e < value);
+        self.ranges.get(index).is_some_and(|&(s, _)| s <= value)
+    }
+
+    /// The number of integers in the set, saturating at `u64::MAX`
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .fold(0, |len, &(s, e)| len.saturating_add(span(s, e)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges, in increasing order
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<Coord>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.ranges() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a, b)), Some(&(c, d))) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (a.max(c), b.min(d));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // the range ending first cannot meet the following ones
+            if b < d {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.ranges() {
+            result.remove(range);
+        }
+        result
+    }
+}
+
+/// The number of integers in `start..=end`, saturating at `u64::MAX`
+pub(crate) fn span(start: Coord, end: Coord) -> u64 {
+    end.abs_diff(start).saturating_add(1)
+}
+
+impl FromIterator<RangeInclusive<Coord>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<Coord>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<Coord>]) -> RangeSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut numbers = set(&[5..=7, 0..=2, 3..=4, 10..=12]);
+        assert_eq!(numbers.ranges().collect::<Vec<_>>(), [0..=7, 10..=12]);
+        assert_eq!(numbers.len(), 11);
+        numbers.remove(2..=10);
+        assert_eq!(numbers, set(&[0..=1, 11..=12]));
+        numbers.remove(-5..=0);
+        // an empty range adds nothing
+        let (start, end) = (3, 1);
+        numbers.insert(start..=end);
+        assert_eq!(numbers, set(&[1..=1, 11..=12]));
+        assert!(numbers.contains(11) && !numbers.contains(10) && !numbers.contains(0));
+        numbers.remove(-100..=100);
+        assert!(numbers.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25, 40..=40]);
+        assert_eq!(a.union(&b), set(&[0..=30, 40..=40]));
+        assert_eq!(a.intersection(&b), set(&[5..=10, 20..=25]));
+        assert_eq!(a.difference(&b), set(&[0..=4, 26..=30]));
+        assert_eq!(b.difference(&a), set(&[11..=19, 40..=40]));
+        // against the integers they hold
+        for x in -5..50 {
+            assert_eq!(a.union(&b).contains(x), a.contains(x) || b.contains(x));
+            assert_eq!(
+                a.intersection(&b).contains(x),
+                a.contains(x) && b.contains(x)
+            );
+            assert_eq!(
+                a.difference(&b).contains(x),
+                a.contains(x) && !b.contains(x)
+            );
+        }
+    }
+
+    #[test]
+    fn test_extremes() {
+        let mut numbers = set(&[Coord::MIN..=-1, 0..=Coord::MAX]);
+        assert_eq!(
+            numbers.ranges().collect::<Vec<_>>(),
+            [Coord::MIN..=Coord::MAX]
+        );
+        // 2^64 integers, one more than a u64 holds
+        assert_eq!(numbers.len(), u64::MAX);
+        numbers.remove(Coord::MIN..=Coord::MIN);
+        numbers.remove(Coord::MAX..=Coord::MAX);
+        assert_eq!(numbers, set(&[Coord::MIN + 1..=Coord::MAX - 1]));
+        assert_eq!(numbers.len(), u64::MAX - 1);
+        numbers.insert(Coord::MIN..=Coord::MAX);
+        assert!(numbers.contains(Coord::MIN) && numbers.contains(Coord::MAX));
+        numbers.remove(Coord::MIN..=Coord::MAX);
+        assert!(numbers.is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::Point;
+use std::ops::Mul;
+
+/// A rotation mapping axes onto axes: coordinate `i` of the result is
+/// coordinate `axes[i]` of the source, multiplied by `signs[i]`
+///
+/// In other words a signed permutation matrix with determinant 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation<const N: usize> {
+    axes: [usize; N],
+    signs: [i8; N],
+}
+
+impl<const N: usize> Rotation<N> {
+    pub fn identity() -> Self {
+        Self {
+            axes: std::array::from_fn(|i| i),
+            signs: [1; N],
+        }
+    }
+
+    /// Panics unless `axes` is a permutation and the result preserves
+    /// orientation
+    pub fn new(axes: [usize; N], signs: [i8; N]) -> Self {
+        let mut seen = [false; N];
+        for &axis in &axes {
+            assert!(axis < N && !seen[axis], "{:?} is not a permutation", axes);
+            seen[axis] = true;
+        }
+        assert!(
+            signs.iter().all(|s| s.abs() == 1),
+            "invalid signs {:?}",
+            signs
+        );
+        let rotation = Self { axes, signs };
+        assert_eq!(rotation.determinant(), 1, "{:?} is a reflection", rotation);
+        rotation
+    }
+
+    pub fn apply(&self, point: &Point<N>) -> Point<N> {
+        Point(std::array::from_fn(|i| {
+            point[self.axes[i]] * self.signs[i] as i64
+        }))
+    }
+
+    /// The rotation applying `other` first, then `self`
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            axes: std::array::from_fn(|i| other.axes[self.axes[i]]),
+            signs: std::array::from_fn(|i| self.signs[i] * other.signs[self.axes[i]]),
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut inverse = *self;
+        for i in 0..N {
+            inverse.axes[self.axes[i]] = i;
+            inverse.signs[self.axes[i]] = self.signs[i];
+        }
+        inverse
+    }
+
+    fn determinant(&self) -> i8 {
+        // the parity of a permutation is the parity of its number of inversions
+        let inversions = (0..N)
+            .flat_map(|i| (i + 1..N).map(move |j| (i, j)))
+            .filter(|&(i, j)| self.axes[i] > self.axes[j])
+            .count();
+        let parity = if inversions % 2 == 0 { 1 } else { -1 };
+        parity * self.signs.iter().product::<i8>()
+    }
+}
+
+impl<const N: usize> Mul<Point<N>> for Rotation<N> {
+    type Output = Point<N>;
+
+    fn mul(self, rhs: Point<N>) -> Self::Output {
+        self.apply(&rhs)
+    }
+}
+
+/// Every rotation of `N` dimensional space mapping axes onto axes, starting
+/// with the identity: 4 in 2D and the 24 orientations of a cube in 3D
+pub fn rotations<const N: usize>() -> Vec<Rotation<N>> {
+    let mut permutations = vec![];
+    permute(&mut std::array::from_fn(|i| i), 0, &mut permutations);
+    let mut result = vec![];
+    for axes in permutations {
+        for flips in 0..1u32 << N {
+            let signs = std::array::from_fn(|i| if flips >> i & 1 == 1 { -1 } else { 1 });
+            let rotation = Rotation { axes, signs };
+            if rotation.determinant() == 1 {
+                result.push(rotation);
+            }
+        }
+    }
+    result
+}
+
+/// Collect every ordering of `axes[start..]`, swapping each axis in front
+fn permute<const N: usize>(axes: &mut [usize; N], start: usize, result: &mut Vec<[usize; N]>) {
+    if start == N {
+        result.push(*axes);
+        return;
+    }
+    for i in start..N {
+        axes.swap(start, i);
+        permute(axes, start + 1, result);
+        axes.swap(start, i);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_group_sizes() {
+        assert_eq!(rotations::<1>().len(), 1);
+        assert_eq!(rotations::<2>().len(), 4);
+        assert_eq!(rotations::<3>().len(), 24);
+        assert_eq!(rotations::<4>().len(), 192);
+        assert_eq!(rotations::<3>()[0], Rotation::identity());
+    }
+
+    #[test]
+    fn test_group_closure() {
+        let group = rotations::<3>();
+        let set: HashSet<_> = group.iter().copied().collect();
+        assert_eq!(set.len(), 24);
+        for a in &group {
+            assert!(set.contains(&a.inverse()));
+            assert_eq!(a.compose(&a.inverse()), Rotation::identity());
+            for b in &group {
+                assert!(set.contains(&a.compose(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let point = Point::new([1, 2, 3]);
+        let group = rotations::<3>();
+        let images: HashSet<_> = group.iter().map(|r| r.apply(&point)).collect();
+        assert_eq!(images.len(), 24);
+        assert!(images.iter().all(|p| p.manhattan() == 6));
+        for a in &group {
+            for b in &group {
+                assert_eq!(a.compose(b).apply(&point), *a * (*b * point));
+            }
+        }
+        // a quarter turn around z
+        let quarter = Rotation::new([1, 0, 2], [-1, 1, 1]);
+        assert_eq!(quarter * Point::new([1, 0, 0]), Point::new([0, 1, 0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reflection() {
+        Rotation::new([1, 0, 2], [1, 1, 1]);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{Delta, NEIGHBORS4, NEIGHBORS8};
+use aoc_core::{ParseError, Source};
+use aoc_image::{Image, Rgb};
+use std::ops::{Index, IndexMut};
+
+/// Column and row of a cell
+pub type Pos = (usize, usize);
+
+/// A rectangular grid stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Panics when `cells` cannot be split into rows of `width`
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {}",
+            cells.len(),
+            width
+        );
+        Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Read a character map, one row per non-empty line
+    ///
+    /// Panics when the lines have different lengths
+    pub fn parse(raw: &str, mut f: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(raw, |c| Some(f(c))).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Read a character map like [`Grid::parse`], reporting the characters
+    /// for which `f` returns `None` and the lines of a different length
+    pub fn try_parse(raw: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let source = Source::new(raw);
+        let mut width = None;
+        let mut cells = vec![];
+        for (index, line) in raw.lines().filter(|line| !line.is_empty()).enumerate() {
+            let len = cells.len();
+            for (i, c) in line.char_indices() {
+                cells.push(f(c).ok_or_else(|| source.unexpected(&line[i..]))?);
+            }
+            let line_width = cells.len() - len;
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(source.error(
+                        line,
+                        format!(
+                            "row {} has {} cells instead of {}",
+                            index + 1,
+                            line_width,
+                            width
+                        ),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        let width = width.unwrap_or(0);
+        Ok(Self {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    fn index(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The position `delta` away from `pos`, if it is inside the grid
+    pub fn offset(&self, (x, y): Pos, (dx, dy): Delta) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The position `delta` away from `pos` on a torus, i.e. wrapping around
+    /// the edges, or `None` if the grid is empty
+    pub fn wrapping_offset(&self, (x, y): Pos, (dx, dy): Delta) -> Option<Pos> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let wrap =
+            |v: usize, d: isize, size: usize| (v as isize + d).rem_euclid(size as isize) as usize;
+        Some((wrap(x, dx, self.width), wrap(y, dy, self.height)))
+    }
+
+    /// Orthogonal neighbors inside the grid
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// Orthogonal and diagonal neighbors inside the grid
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    pub fn wrapping_neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4
+            .iter()
+            .filter_map(move |&delta| self.wrapping_offset(pos, delta))
+    }
+
+    pub fn wrapping_neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8
+            .iter()
+            .filter_map(move |&delta| self.wrapping_offset(pos, delta))
+    }
+
+    /// Draw one character per cell, ending every row with a newline
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut result = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows().take(self.height) {
+            result.extend(row.iter().map(&mut f));
+            result.push('\n');
+        }
+        result
+    }
+
+    /// Draw one pixel per cell, coloured by `f`
+    pub fn to_image(&self, mut f: impl FnMut(&T) -> Rgb) -> Image {
+        Image::from_fn(self.width, self.height, |x, y| f(&self[(x, y)]))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "{:?} is outside of the grid", pos);
+        &self.cells[self.index(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "{:?} is outside of the grid", pos);
+        let index = self.index(pos);
+        &mut self.cells[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "\
+#..
+.#.
+..#
+#.#
+";
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = Grid::parse(MAP, |c| c == '#');
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert!(grid[(0, 0)] && grid[(2, 3)] && !grid[(1, 3)]);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), MAP);
+        let image = grid.to_image(|&b| if b { [255; 3] } else { [0; 3] });
+        assert_eq!((image.width(), image.height()), (3, 4));
+        assert_eq!((image[(1, 1)], image[(1, 3)]), ([255; 3], [0; 3]));
+        assert_eq!(grid.values().filter(|&&b| b).count(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 has 2 cells instead of 3")]
+    fn test_parse_ragged() {
+        Grid::parse("abc\nde\n", |c| c);
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let error = Grid::try_parse("#.\n.x\n", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+        assert_eq!(error.unwrap_err(), ParseError::new(2, 2, "unexpected `x`"));
+        let error = Grid::try_parse("ab\n\nabc", Some).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors4((2, 1)).count(), 3);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = Grid::new(4, 3, 0);
+        assert_eq!(grid.wrapping_offset((0, 0), (-1, -1)), Some((3, 2)));
+        assert_eq!(grid.wrapping_offset((3, 2), (1, 1)), Some((0, 0)));
+        assert_eq!(grid.wrapping_offset((1, 1), (9, 0)), Some((2, 1)));
+        let around: Vec<_> = grid.wrapping_neighbors4((0, 0)).collect();
+        assert_eq!(around, [(0, 2), (1, 0), (0, 1), (3, 0)]);
+        assert_eq!(grid.wrapping_neighbors8((0, 0)).count(), 8);
+        // nowhere to wrap to
+        let empty = Grid::new(0, 3, 0);
+        assert_eq!(empty.wrapping_offset((0, 0), (1, 1)), None);
+        assert_eq!(Grid::new(4, 0, 0).wrapping_neighbors8((0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_from_fn_and_map() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid, Grid::from_vec(3, vec![0, 1, 2, 10, 11, 12]));
+        assert_eq!(grid.map(|v| v % 10)[(2, 1)], 2);
+        let rows: Vec<_> = grid.rows().collect();
+        assert_eq!(rows, [&[0, 1, 2], &[10, 11, 12]]);
+    }
+}
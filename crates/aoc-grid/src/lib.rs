@@ -0,0 +1,28 @@
+//! Dense and sparse 2D grids, neighbourhoods and parsing from puzzle text
+//!
+//! Dense grids are indexed by `(x, y)` with `y` growing downwards, as puzzle
+//! maps are read. Sparse grids accept any signed position.
+
+mod dense;
+mod sparse;
+
+pub use dense::{Grid, Pos};
+pub use sparse::{SparseGrid, SparsePos};
+
+/// An offset between two positions
+pub type Delta = (isize, isize);
+
+/// Up, right, down and left
+pub const NEIGHBORS4: [Delta; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Clockwise from up, including diagonals
+pub const NEIGHBORS8: [Delta; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
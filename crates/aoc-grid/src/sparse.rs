@@ -0,0 +1,168 @@
+use crate::{Delta, NEIGHBORS4, NEIGHBORS8};
+use aoc_image::{Image, Rgb, BLACK};
+use std::collections::HashMap;
+use std::ops::Index;
+
+/// A position on an unbounded plane
+pub type SparsePos = (i64, i64);
+
+/// Cells of an unbounded plane, only the visited ones are stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<SparsePos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a character map, keeping the cells for which `f` returns a value
+    pub fn parse(raw: &str, mut f: impl FnMut(char) -> Option<T>) -> Self {
+        let mut cells = HashMap::new();
+        for (y, line) in raw.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = f(c) {
+                    cells.insert((x as i64, y as i64), value);
+                }
+            }
+        }
+        Self { cells }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: SparsePos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: SparsePos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn insert(&mut self, pos: SparsePos, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    /// The cell at `pos`, inserting `default` if it has never been visited
+    pub fn entry(&mut self, pos: SparsePos, default: T) -> &mut T {
+        self.cells.entry(pos).or_insert(default)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SparsePos, &T)> {
+        self.cells.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    /// The smallest and largest coordinates of the stored cells
+    pub fn bounds(&self) -> Option<(SparsePos, SparsePos)> {
+        let mut positions = self.cells.keys();
+        let &first = positions.next()?;
+        Some(
+            positions.fold((first, first), |((x0, y0), (x1, y1)), &(x, y)| {
+                ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y)))
+            }),
+        )
+    }
+
+    /// Orthogonal neighbors, visited or not
+    pub fn neighbors4(&self, pos: SparsePos) -> impl Iterator<Item = SparsePos> {
+        NEIGHBORS4.iter().map(move |&delta| offset(pos, delta))
+    }
+
+    /// Orthogonal and diagonal neighbors, visited or not
+    pub fn neighbors8(&self, pos: SparsePos) -> impl Iterator<Item = SparsePos> {
+        NEIGHBORS8.iter().map(move |&delta| offset(pos, delta))
+    }
+
+    /// Draw the bounding box of the stored cells row by row, `f` receives
+    /// `None` for the cells that were never visited
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> String {
+        let mut result = String::new();
+        if let Some(((x0, y0), (x1, y1))) = self.bounds() {
+            for y in y0..=y1 {
+                result.extend((x0..=x1).map(|x| f(self.get((x, y)))));
+                result.push('\n');
+            }
+        }
+        result
+    }
+
+    /// Draw the bounding box of the stored cells with one pixel per cell, `f`
+    /// receives `None` for the cells that were never visited
+    pub fn to_image(&self, mut f: impl FnMut(Option<&T>) -> Rgb) -> Image {
+        match self.bounds() {
+            Some(((x0, y0), (x1, y1))) => {
+                Image::from_fn((x1 - x0 + 1) as usize, (y1 - y0 + 1) as usize, |x, y| {
+                    f(self.get((x0 + x as i64, y0 + y as i64)))
+                })
+            }
+            None => Image::new(0, 0, BLACK),
+        }
+    }
+}
+
+fn offset((x, y): SparsePos, (dx, dy): Delta) -> SparsePos {
+    (x + dx as i64, y + dy as i64)
+}
+
+impl<T> Index<SparsePos> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: SparsePos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} has never been visited", pos))
+    }
+}
+
+impl<T> FromIterator<(SparsePos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (SparsePos, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_image::WHITE;
+
+    #[test]
+    fn test_render() {
+        let mut grid = SparseGrid::new();
+        grid.insert((-1, -1), '#');
+        grid.insert((1, 0), '.');
+        *grid.entry((0, 0), '.') = '#';
+        assert_eq!(grid[(0, 0)], '#');
+        assert_eq!(grid.bounds(), Some(((-1, -1), (1, 0))));
+        assert_eq!(grid.render(|c| c.copied().unwrap_or(' ')), "#  \n #.\n");
+        let image = grid.to_image(|c| if c == Some(&'#') { WHITE } else { BLACK });
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image[(0, 0)], WHITE);
+        assert_eq!(image[(2, 0)], BLACK);
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = SparseGrid::parse(".#\n#.\n", |c| (c == '#').then_some(()));
+        assert_eq!(grid.len(), 2);
+        assert!(grid.get((1, 0)).is_some() && grid.get((0, 0)).is_none());
+        assert_eq!(grid.neighbors8((0, 0)).count(), 8);
+        assert!(grid.neighbors4((0, 0)).any(|pos| pos == (0, -1)));
+        assert_eq!(SparseGrid::<()>::new().render(|_| '#'), "");
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{Format, Image};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Writes every `every`th state of a simulation to a directory, as text or
+/// as images named after their step, e.g. `000042.txt`
+///
+/// Simulations call [`Recorder::record`] after each step; states are only
+/// drawn when they are kept. Write errors are kept until
+/// [`Recorder::finish`] so that the simulation itself does not have to
+/// handle them.
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    every: usize,
+    image: Option<(Format, usize)>,
+    frames: usize,
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    /// Record text frames into `dir`, which is created if needed
+    pub fn new(dir: impl Into<PathBuf>, every: usize) -> io::Result<Self> {
+        assert!(every > 0, "frames must be kept every 1 step or more");
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            every,
+            image: None,
+            frames: 0,
+            error: None,
+        })
+    }
+
+    /// Record images in `format` instead, with `scale` × `scale` pixels per
+    /// pixel of the drawn states
+    pub fn images(self, format: Format, scale: usize) -> Self {
+        Self {
+            image: Some((format, scale)),
+            ..self
+        }
+    }
+
+    /// Keep the state of `step` if it is one of every `every`th, drawn by
+    /// `text` or `image` depending on the frame format
+    pub fn record(
+        &mut self,
+        step: usize,
+        text: impl FnOnce() -> String,
+        image: impl FnOnce() -> Image,
+    ) {
+        if self.error.is_some() || !step.is_multiple_of(self.every) {
+            return;
+        }
+        let result = match self.image {
+            Some((format, scale)) => {
+                let path = self.dir.join(format!("{:06}.{}", step, format.extension()));
+                image().scaled(scale).save(path)
+            }
+            None => fs::write(self.dir.join(format!("{:06}.txt", step)), text()),
+        };
+        match result {
+            Ok(()) => self.frames += 1,
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    /// The number of frames written, or the first error
+    pub fn finish(self) -> io::Result<usize> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.frames),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::WHITE;
+    use std::env;
+
+    #[test]
+    fn test_recorder() {
+        let dir = env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut recorder = Recorder::new(dir.join("text"), 2).unwrap();
+        for step in 0..5 {
+            recorder.record(step, || format!("step {}\n", step), || unreachable!());
+        }
+        assert_eq!(recorder.finish().unwrap(), 3);
+        assert_eq!(
+            fs::read_to_string(dir.join("text/000004.txt")).unwrap(),
+            "step 4\n"
+        );
+        assert!(!dir.join("text/000003.txt").exists());
+
+        let mut recorder = Recorder::new(dir.join("ppm"), 1)
+            .unwrap()
+            .images(Format::Ppm, 3);
+        recorder.record(7, || unreachable!(), || Image::new(2, 1, WHITE));
+        assert_eq!(recorder.finish().unwrap(), 1);
+        let frame = fs::read(dir.join("ppm/000007.ppm")).unwrap();
+        assert!(frame.starts_with(b"P6\n6 3\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+//! RGB images and their netpbm encodings, to look at puzzle states too big
+//! for the terminal
+//!
+//! Images are written in the binary variants of the formats: PBM (`P4`) for
+//! black and white, PGM (`P5`) for grayscale and PPM (`P6`) for colours.
+//! Simulations can dump their successive states with a [`Recorder`].
+
+mod frames;
+mod netpbm;
+
+pub use frames::Recorder;
+pub use netpbm::Format;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::{Index, IndexMut};
+use std::path::Path;
+
+/// Red, green and blue components
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Perceived brightness of `color`, from 0 for black to 255 for white
+pub fn luma([r, g, b]: Rgb) -> u8 {
+    ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+}
+
+/// Pixels stored row by row, `(x, y)` with `y` growing downwards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> Rgb) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Every pixel blown up into a `factor` × `factor` square
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self[(x / factor, y / factor)]
+        })
+    }
+
+    pub fn write(&self, format: Format, writer: impl Write) -> io::Result<()> {
+        netpbm::write(self, format, writer)
+    }
+
+    /// Write the image to `path`, in the format given by its extension
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a .pbm, .pgm or .ppm file", path.display()),
+            )
+        })?;
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(format, &mut writer)?;
+        writer.flush()
+    }
+}
+
+impl Index<(usize, usize)> for Image {
+    type Output = Rgb;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the image",
+            x,
+            y
+        );
+        &self.pixels[y * self.width + x]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Image {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the image",
+            x,
+            y
+        );
+        &mut self.pixels[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_image() {
+        let mut image = Image::new(2, 1, BLACK);
+        image[(1, 0)] = WHITE;
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 2));
+        assert_eq!(scaled.rows().nth(1).unwrap(), [BLACK, BLACK, WHITE, WHITE]);
+        assert_eq!(Image::from_fn(2, 1, |x, _| [x as u8; 3]), {
+            let mut expected = Image::new(2, 1, BLACK);
+            expected[(1, 0)] = [1; 3];
+            expected
+        });
+        assert_eq!((luma(BLACK), luma(WHITE), luma([255, 0, 0])), (0, 255, 76));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{luma, Image};
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One bit per pixel, set for the dark ones
+    Pbm,
+    /// One byte of brightness per pixel
+    Pgm,
+    /// Three bytes of red, green and blue per pixel
+    Ppm,
+}
+
+impl Format {
+    /// The format named by the extension of `path`
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "pbm" => Some(Self::Pbm),
+            "pgm" => Some(Self::Pgm),
+            "ppm" => Some(Self::Ppm),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Pbm => "pbm",
+            Self::Pgm => "pgm",
+            Self::Ppm => "ppm",
+        }
+    }
+
+    fn magic(self) -> &'static str {
+        match self {
+            Self::Pbm => "P4",
+            Self::Pgm => "P5",
+            Self::Ppm => "P6",
+        }
+    }
+}
+
+pub fn write(image: &Image, format: Format, mut writer: impl Write) -> io::Result<()> {
+    write!(
+        writer,
+        "{}\n{} {}\n",
+        format.magic(),
+        image.width(),
+        image.height()
+    )?;
+    if format != Format::Pbm {
+        writeln!(writer, "255")?;
+    }
+    let mut row_bytes = vec![];
+    for row in image.rows() {
+        row_bytes.clear();
+        match format {
+            // rows are padded to whole bytes, the first pixel in the high bit
+            Format::Pbm => row_bytes.extend(row.chunks(8).map(|pixels| {
+                pixels.iter().enumerate().fold(0u8, |byte, (i, &color)| {
+                    byte | (u8::from(luma(color) < 128) << (7 - i))
+                })
+            })),
+            Format::Pgm => row_bytes.extend(row.iter().map(|&color| luma(color))),
+            Format::Ppm => row_bytes.extend(row.iter().flatten()),
+        }
+        writer.write_all(&row_bytes)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BLACK, WHITE};
+
+    fn encode(image: &Image, format: Format) -> Vec<u8> {
+        let mut bytes = vec![];
+        image.write(format, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_formats() {
+        let mut image = Image::new(9, 2, WHITE);
+        image[(0, 0)] = BLACK;
+        image[(8, 1)] = [200, 0, 10];
+        assert_eq!(
+            encode(&image, Format::Pbm),
+            b"P4\n9 2\n\x80\x00\x00\x80".to_vec()
+        );
+        let pgm = encode(&image, Format::Pgm);
+        assert!(pgm.starts_with(b"P5\n9 2\n255\n\x00\xff"));
+        assert_eq!(pgm.len(), 11 + 18);
+        assert_eq!(pgm.last(), Some(&60));
+        let ppm = encode(&image, Format::Ppm);
+        assert!(ppm.starts_with(b"P6\n9 2\n255\n\x00\x00\x00\xff"));
+        assert!(ppm.ends_with(&[200, 0, 10]));
+        assert_eq!(ppm.len(), 11 + 54);
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(Format::from_path("out.ppm"), Some(Format::Ppm));
+        assert_eq!(Format::from_path("dir/day20.PBM"), Some(Format::Pbm));
+        assert_eq!(Format::from_path("out.png"), None);
+        assert_eq!(Format::from_path("pgm"), None);
+    }
+}
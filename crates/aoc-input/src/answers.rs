@@ -0,0 +1,207 @@
+//! Expected answers of a year, stored in `{root}/{year}/answers.txt`
+//!
+//! Each line holds a day, a part, an input variant and the answer, separated
+//! by single spaces. The answer runs until the end of the line, newlines in
+//! multi-line answers are written as `\n` and backslashes as `\\`.
+//!
+//! ```text
+//! # day part variant answer
+//! 1 1 example 7
+//! 1 1 input 1162
+//! 13 2 example #####\n#   #\n#   #\n#   #\n#####
+//! ```
+
+use crate::Variant;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: u8,
+    pub variant: Variant,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    entries: Vec<Expected>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAnswersError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseAnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseAnswersError {}
+
+impl FromStr for Answers {
+    type Err = ParseAnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries: Vec<Expected> = vec![];
+        for (index, line) in s.lines().enumerate() {
+            let error = |message: String| ParseAnswersError {
+                line: index + 1,
+                message,
+            };
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(4, ' ');
+            let mut next_field = |name: &str| {
+                fields
+                    .next()
+                    .ok_or_else(|| error(format!("missing {}", name)))
+            };
+            let day = next_field("day")?;
+            let day = day
+                .parse()
+                .map_err(|_| error(format!("invalid day `{}`", day)))?;
+            let part = next_field("part")?;
+            let part = match part {
+                "1" => 1,
+                "2" => 2,
+                _ => return Err(error(format!("invalid part `{}`", part))),
+            };
+            let variant = next_field("variant")?;
+            let variant = variant
+                .parse()
+                .map_err(|_| error(format!("invalid variant `{}`", variant)))?;
+            let answer = unescape(next_field("answer")?).map_err(error)?;
+            let expected = Expected {
+                day,
+                part,
+                variant,
+                answer,
+            };
+            if entries.iter().any(|e| {
+                (e.day, e.part, e.variant) == (expected.day, expected.part, expected.variant)
+            }) {
+                return Err(error(format!(
+                    "day {} part {} {} is listed twice",
+                    day, part, variant
+                )));
+            }
+            entries.push(expected);
+        }
+        Ok(Self { entries })
+    }
+}
+
+impl Answers {
+    pub fn get(&self, day: u8, part: u8, variant: Variant) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| (e.day, e.part, e.variant) == (day, part, variant))
+            .map(|e| e.answer.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Expected> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('\\') => result.push('\\'),
+            Some(c) => return Err(format!("unknown escape `\\{}`", c)),
+            None => return Err("dangling `\\` at the end of the answer".to_string()),
+        }
+    }
+    Ok(result)
+}
+
+/// Escape an answer so that it fits on one line of the manifest
+pub fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Ignore the blank margins around multi-line answers and trailing spaces of
+/// their lines, which are easily lost when editing the manifest
+pub fn normalize(answer: &str) -> String {
+    let lines: Vec<_> = answer.lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(0);
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(0, |i| i + 1);
+    lines[start..end.max(start)].join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = "\
+# day part variant answer
+1 1 example 7
+1 1 input 1162
+
+13 2 example #\\n \\\\#
+25 2 input Happy Holiday
+"
+        .parse()
+        .unwrap();
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers.get(1, 1, Variant::Example), Some("7"));
+        assert_eq!(answers.get(1, 1, Variant::Input), Some("1162"));
+        assert_eq!(answers.get(1, 2, Variant::Input), None);
+        assert_eq!(answers.get(13, 2, Variant::Example), Some("#\n \\#"));
+        assert_eq!(answers.get(25, 2, Variant::Input), Some("Happy Holiday"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |raw: &str| raw.parse::<Answers>().unwrap_err();
+        assert_eq!(error("1 1 input").message, "missing answer");
+        assert_eq!(error("# comment\n1 3 input 5").line, 2);
+        assert_eq!(error("1 3 input 5").message, "invalid part `3`");
+        assert_eq!(error("x 1 input 5").message, "invalid day `x`");
+        assert_eq!(error("1 1 real 5").message, "invalid variant `real`");
+        assert_eq!(error("1 1 input 5\\t").message, "unknown escape `\\t`");
+        assert_eq!(
+            error("1 1 input 5\n1 1 input 6").message,
+            "day 1 part 1 input is listed twice"
+        );
+    }
+
+    #[test]
+    fn test_escape_roundtrip() {
+        let answer = "#  #\n\\ #\n";
+        assert_eq!(unescape(&escape(answer)).unwrap(), answer);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\n#  # \n ## \n\n"), "#  #\n ##");
+        assert_eq!(normalize("42"), "42");
+        assert_eq!(normalize("\n\n"), "");
+    }
+}
@@ -0,0 +1,281 @@
+//! Locating and reading puzzle inputs
+//!
+//! Inputs are looked up under a data root, which is the repository root unless
+//! overridden by the `AOC_DATA` environment variable or [`Resolver::with_root`].
+//! For each (year, day, variant) the following layouts are tried in order:
+//!
+//! ```text
+//! {root}/{year}/day{day}.txt
+//! {root}/{year}/data/day{day}.txt
+//! {root}/{year}/packages/day{day}/data/input.txt
+//! ```
+//!
+//! The example variant uses the same paths with `.dbg.txt` instead of `.txt`.
+//! Expected answers live next to the inputs, see [`answers`].
+
+pub mod answers;
+
+use answers::{Answers, ParseAnswersError};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const DATA_ENV: &str = "AOC_DATA";
+
+const REPOSITORY_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+fn repository_root() -> PathBuf {
+    let root = Path::new(REPOSITORY_ROOT);
+    root.canonicalize().unwrap_or_else(|_| root.to_owned())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// The personal puzzle input
+    Input,
+    /// The example from the puzzle description, stored as `.dbg.txt`
+    Example,
+}
+
+impl Variant {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Input => "txt",
+            Self::Example => "dbg.txt",
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(Self::Input),
+            "example" => Ok(Self::Example),
+            _ => Err(format!("unknown variant `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Input => f.pad("input"),
+            Self::Example => f.pad("example"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// None of the candidate paths exists
+    Missing {
+        year: u16,
+        day: u8,
+        variant: Variant,
+        candidates: Vec<PathBuf>,
+    },
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// The answers manifest is malformed
+    Answers {
+        path: PathBuf,
+        source: ParseAnswersError,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing {
+                year,
+                day,
+                variant,
+                candidates,
+            } => {
+                write!(f, "no {} found for {} day {}, tried:", variant, year, day)?;
+                for candidate in candidates {
+                    write!(f, "\n  {}", candidate.display())?;
+                }
+                Ok(())
+            }
+            Self::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "cannot read {}: {}", path.display(), source),
+            Self::Io { path: None, source } => write!(f, "cannot read stdin: {}", source),
+            Self::Answers { path, source } => write!(f, "{}:{}", path.display(), source),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Missing { .. } => None,
+            Self::Io { source, .. } => Some(source),
+            Self::Answers { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<InputError> for io::Error {
+    fn from(err: InputError) -> Self {
+        let kind = match &err {
+            InputError::Missing { .. } => io::ErrorKind::NotFound,
+            InputError::Io { source, .. } => source.kind(),
+            InputError::Answers { .. } => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    root: PathBuf,
+}
+
+impl Resolver {
+    /// Use `AOC_DATA` as the data root when it is set, otherwise the repository
+    pub fn from_env() -> Self {
+        match env::var_os(DATA_ENV) {
+            Some(root) if !root.is_empty() => Self::with_root(root),
+            _ => Self::with_root(repository_root()),
+        }
+    }
+
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Every path where the input may live, by priority
+    pub fn candidates(&self, year: u16, day: u8, variant: Variant) -> Vec<PathBuf> {
+        let year_dir = self.root.join(year.to_string());
+        let extension = variant.extension();
+        vec![
+            year_dir.join(format!("day{}.{}", day, extension)),
+            year_dir.join(format!("data/day{}.{}", day, extension)),
+            year_dir.join(format!("packages/day{}/data/input.{}", day, extension)),
+        ]
+    }
+
+    pub fn locate(&self, year: u16, day: u8, variant: Variant) -> Result<PathBuf, InputError> {
+        let candidates = self.candidates(year, day, variant);
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(path.clone()),
+            None => Err(InputError::Missing {
+                year,
+                day,
+                variant,
+                candidates,
+            }),
+        }
+    }
+
+    pub fn read(&self, year: u16, day: u8, variant: Variant) -> Result<String, InputError> {
+        read_file(self.locate(year, day, variant)?)
+    }
+
+    pub fn answers_path(&self, year: u16) -> PathBuf {
+        self.root.join(format!("{}/answers.txt", year))
+    }
+
+    /// The expected answers of a year, empty when it has no manifest
+    pub fn answers(&self, year: u16) -> Result<Answers, InputError> {
+        let path = self.answers_path(year);
+        if !path.is_file() {
+            return Ok(Answers::default());
+        }
+        read_file(&path)?
+            .parse()
+            .map_err(|source| InputError::Answers { path, source })
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// Read an input with the default [`Resolver`]
+pub fn read(year: u16, day: u8, variant: Variant) -> Result<String, InputError> {
+    Resolver::from_env().read(year, day, variant)
+}
+
+pub fn read_file(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|source| InputError::Io {
+        path: Some(path.to_owned()),
+        source,
+    })
+}
+
+pub fn read_stdin() -> Result<String, InputError> {
+    let mut raw = String::new();
+    io::stdin()
+        .read_to_string(&mut raw)
+        .map_err(|source| InputError::Io { path: None, source })?;
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_locate_year_layouts() {
+        let resolver = Resolver::with_root(REPOSITORY_ROOT);
+        let locate = |year, day, variant| {
+            let path = resolver.locate(year, day, variant).unwrap();
+            path.strip_prefix(REPOSITORY_ROOT).unwrap().to_owned()
+        };
+        assert_eq!(
+            locate(2019, 10, Variant::Input),
+            Path::new("2019/packages/day10/data/input.txt")
+        );
+        assert_eq!(
+            locate(2020, 1, Variant::Input),
+            Path::new("2020/data/day1.txt")
+        );
+        assert_eq!(
+            locate(2021, 23, Variant::Example),
+            Path::new("2021/data/day23.dbg.txt")
+        );
+    }
+
+    #[test]
+    fn test_missing() {
+        let resolver = Resolver::with_root(REPOSITORY_ROOT);
+        let err = resolver.read(2021, 16, Variant::Example).unwrap_err();
+        match &err {
+            InputError::Missing { candidates, .. } => assert_eq!(candidates.len(), 3),
+            _ => panic!("unexpected error {:?}", err),
+        }
+        let message = err.to_string();
+        assert!(message.starts_with("no example found for 2021 day 16, tried:"));
+        assert!(message.contains("day16.dbg.txt"));
+    }
+
+    #[test]
+    fn test_flat_layout_first() {
+        let root = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(root.join("2021/data")).unwrap();
+        fs::write(root.join("2021/day1.txt"), "flat").unwrap();
+        fs::write(root.join("2021/data/day1.txt"), "nested").unwrap();
+        let resolver = Resolver::with_root(&root);
+        assert_eq!(resolver.read(2021, 1, Variant::Input).unwrap(), "flat");
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The primitive integers the number theory functions work on
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    /// The absolute value, the identity for unsigned integers
+    fn magnitude(self) -> Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// `(self + other) % modulus` for operands in `0..modulus`, without
+    /// overflowing
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        if self >= modulus - other {
+            self - (modulus - other)
+        } else {
+            self + other
+        }
+    }
+
+    /// `(self * other) % modulus` for operands in `0..modulus`, without
+    /// overflowing
+    ///
+    /// Doubles and adds by default, integers which fit in a wider type
+    /// multiply there instead.
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        let mut product = Self::ZERO;
+        while b > Self::ZERO {
+            if b % Self::TWO == Self::ONE {
+                product = product.add_mod(a, modulus);
+            }
+            a = a.add_mod(a, modulus);
+            b = b / Self::TWO;
+        }
+        product
+    }
+}
+
+/// Integers which may be negative, as Bézout coefficients are
+pub trait Signed: Integer + Neg<Output = Self> {
+    /// The remainder in `0..modulus`, for a positive `modulus`
+    fn rem_euclid(self, modulus: Self) -> Self;
+}
+
+macro_rules! integer {
+    ($($t:ty => $magnitude:expr, $wide:ty);* $(;)?) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TWO: Self = 2;
+
+                fn magnitude(self) -> Self {
+                    $magnitude(self)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                    (self as $wide * other as $wide % modulus as $wide) as $t
+                }
+            }
+        )*
+    };
+}
+
+integer! {
+    i32 => i32::abs, i64;
+    i64 => i64::abs, i128;
+    isize => isize::abs, i128;
+    u32 => std::convert::identity, u64;
+    u64 => std::convert::identity, u128;
+    usize => std::convert::identity, u128;
+}
+
+macro_rules! wide_integer {
+    ($($t:ty => $magnitude:expr);* $(;)?) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TWO: Self = 2;
+
+                fn magnitude(self) -> Self {
+                    $magnitude(self)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+wide_integer! {
+    i128 => i128::abs;
+    u128 => std::convert::identity;
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(
+            impl Signed for $t {
+                fn rem_euclid(self, modulus: Self) -> Self {
+                    <$t>::rem_euclid(self, modulus)
+                }
+            }
+        )*
+    };
+}
+
+signed!(i32, i64, isize, i128);
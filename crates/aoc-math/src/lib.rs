@@ -0,0 +1,249 @@
+//! Number theory on primitive integers: gcd and lcm, Bézout coefficients,
+//! modular inverses and powers, and the Chinese remainder theorem
+//!
+//! Every function is generic over the integer type, `i128` included.
+//! Products which may not fit are computed without overflowing, or reported
+//! as `None` when the result itself does not fit.
+
+mod int;
+
+pub use int::{Integer, Signed};
+
+/// The greatest common divisor, never negative, `gcd(0, 0)` being 0
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.magnitude(), b.magnitude());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, never negative, or `None` if it overflows
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a.magnitude() / gcd(a, b)).checked_mul(b.magnitude())
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, `g` being `gcd(a, b)`
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if `a` and
+/// the positive `modulus` are coprime
+pub fn mod_inverse<T: Signed>(a: T, modulus: T) -> Option<T> {
+    assert!(modulus > T::ZERO, "the modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == T::ONE).then(|| x.rem_euclid(modulus))
+}
+
+/// `base` to the power of `exponent`, modulo the positive `modulus`, in
+/// `0..modulus`
+pub fn mod_pow<T: Signed>(base: T, exponent: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "the modulus must be positive");
+    assert!(exponent >= T::ZERO, "the exponent must not be negative");
+    let mut base = base.rem_euclid(modulus);
+    let mut exponent = exponent;
+    let mut result = T::ONE.rem_euclid(modulus);
+    while exponent > T::ZERO {
+        if exponent % T::TWO == T::ONE {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exponent = exponent / T::TWO;
+    }
+    result
+}
+
+/// The Chinese remainder theorem: the `(x, m)` such that the numbers
+/// satisfying every `(residue, modulus)` congruence are exactly those
+/// congruent to `x` modulo `m`, `x` being in `0..m`
+///
+/// The moduli must be positive but need not be coprime. `None` if the
+/// congruences contradict each other or `m` overflows.
+pub fn crt<T: Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let mut solution = (T::ZERO, T::ONE);
+    for (residue, modulus) in congruences {
+        assert!(modulus > T::ZERO, "the moduli must be positive");
+        let (r1, m1) = solution;
+        let r2 = residue.rem_euclid(modulus);
+        let g = gcd(m1, modulus);
+        let difference = r2 - r1;
+        if difference % g != T::ZERO {
+            return None;
+        }
+        // r1 + m1 * k ≡ r2 (mod modulus) <=> (m1 / g) * k ≡ difference / g (mod modulus / g)
+        let reduced = modulus / g;
+        let k = (difference / g)
+            .rem_euclid(reduced)
+            .mul_mod(mod_inverse(m1 / g, reduced)?, reduced);
+        let m = (m1 / g).checked_mul(modulus)?;
+        // m1 * k < m as k < modulus / g, then r1 < m1 keeps the sum below m
+        solution = (r1 + m1 * k, m);
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_rand::Rng;
+
+    const CASES: usize = 2000;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(-4i32, 6), Some(12));
+        assert_eq!(lcm(0, 5), Some(0));
+        // m * n overflows, m / gcd * n does not
+        assert_eq!(lcm(1u64 << 62, 1 << 61), Some(1 << 62));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm(i128::MAX, 2), None);
+
+        let mut rng = Rng::new(46);
+        for _ in 0..CASES {
+            let (a, b) = (
+                rng.range(-(1 << 40)..=1 << 40),
+                rng.range(-(1 << 40)..=1 << 40),
+            );
+            let g = gcd(a, b);
+            if g == 0 {
+                assert_eq!((a, b), (0, 0));
+                continue;
+            }
+            assert!(g > 0 && a % g == 0 && b % g == 0);
+            assert_eq!(gcd(a / g, b / g), 1);
+            let wide = a.unsigned_abs() as i128 / g as i128 * b.unsigned_abs() as i128;
+            assert_eq!(lcm(a, b), i64::try_from(wide).ok());
+            assert_eq!(lcm(a as i128, b as i128), Some(wide));
+        }
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240i64, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(0i64, -5), (5, 0, -1));
+        let mut rng = Rng::new(1);
+        for _ in 0..CASES {
+            let (a, b) = (
+                rng.range(-(1 << 50)..=1 << 50),
+                rng.range(-(1 << 50)..=1 << 50),
+            );
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+            let (g, x, y) = extended_gcd(a as i128 * 1_000_003, b as i128 * 1_000_003);
+            assert_eq!(g, gcd(a, b) as i128 * 1_000_003);
+            assert_eq!(a as i128 * 1_000_003 * x + b as i128 * 1_000_003 * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i64, 9), None);
+        assert_eq!(mod_inverse(5i64, 1), Some(0));
+        let mut rng = Rng::new(2);
+        for _ in 0..CASES {
+            let modulus = rng.range(1..=1 << 40);
+            let a = rng.range(-(1 << 50)..=1 << 50);
+            match mod_inverse(a, modulus) {
+                Some(x) => {
+                    assert!((0..modulus).contains(&x));
+                    assert_eq!(
+                        (a as i128 * x as i128).rem_euclid(modulus as i128),
+                        1 % modulus as i128
+                    );
+                }
+                None => assert_ne!(gcd(a, modulus), 1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4i64, 13, 497), 445);
+        assert_eq!(mod_pow(-2i64, 3, 5), 2);
+        assert_eq!(mod_pow(7i64, 0, 1), 0);
+        // Fermat's little theorem on Mersenne primes, whose squares overflow
+        let p61 = (1i64 << 61) - 1;
+        let p89 = (1i128 << 89) - 1;
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let a = rng.range(1..=p61 - 1);
+            assert_eq!(mod_pow(a, p61 - 1, p61), 1);
+            let a = a as i128 * rng.range(1..=1 << 20) as i128 % p89;
+            assert_eq!(mod_pow(a, p89 - 1, p89), 1);
+            assert_eq!(mod_pow(a, p89, p89), a);
+        }
+        for _ in 0..CASES {
+            let (base, exponent, modulus) = (
+                rng.range(-1000..=1000),
+                rng.range(0..=19),
+                rng.range(1..=1000),
+            );
+            let naive =
+                (0..exponent).fold(1 % modulus, |power, _| (power * base).rem_euclid(modulus));
+            assert_eq!(mod_pow(base, exponent, modulus), naive);
+            assert_eq!(
+                mod_pow(base as i128, exponent as i128, modulus as i128),
+                naive as i128
+            );
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt(Vec::<(i64, i64)>::new()), Some((0, 1)));
+        // the product of the moduli overflows i64 but not i128
+        let primes = [1_000_000_007i64, 998_244_353, 1_000_000_009];
+        assert_eq!(crt(primes.iter().map(|&p| (5, p))), None);
+        let wide = crt(primes.iter().map(|&p| (5i128, p as i128))).unwrap();
+        assert_eq!(wide, (5, primes.iter().map(|&p| p as i128).product()));
+
+        let mut rng = Rng::new(4);
+        for _ in 0..CASES {
+            let moduli: Vec<_> = (0..3).map(|_| rng.range(1..=12)).collect();
+            let residues: Vec<_> = (0..3).map(|_| rng.range(-30..=30)).collect();
+            let congruences: Vec<_> = residues
+                .iter()
+                .copied()
+                .zip(moduli.iter().copied())
+                .collect();
+            let m = moduli.iter().fold(1, |m, &n| lcm(m, n).unwrap());
+            let solutions: Vec<_> = (0..m)
+                .filter(|x| congruences.iter().all(|&(r, n)| (x - r).rem_euclid(n) == 0))
+                .collect();
+            match crt(congruences.iter().copied()) {
+                Some((x, modulus)) => {
+                    assert_eq!(modulus, m);
+                    assert_eq!(solutions, [x]);
+                }
+                None => assert!(solutions.is_empty()),
+            }
+        }
+    }
+}
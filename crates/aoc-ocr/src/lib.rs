@@ -0,0 +1,134 @@
+//! Reading the block letters some puzzles draw their answers with
+//!
+//! Letters are told apart by the blank columns between them, so the drawing
+//! may start anywhere and the letter spacing does not matter. The font is
+//! picked from the height of the drawing: 6 rows for the usual 4×6 letters,
+//! 10 rows for the larger 6×10 ones.
+
+mod font;
+
+use aoc_grid::{Grid, SparsePos};
+use font::{Font, FONTS};
+use std::collections::HashSet;
+
+/// The text drawn by the lit `dots`, or `None` if some letter is unknown
+pub fn read(dots: impl IntoIterator<Item = SparsePos>) -> Option<String> {
+    let dots: HashSet<SparsePos> = dots.into_iter().collect();
+    let x0 = dots.iter().map(|&(x, _)| x).min()?;
+    let x1 = dots.iter().map(|&(x, _)| x).max()?;
+    let y0 = dots.iter().map(|&(_, y)| y).min()?;
+    let y1 = dots.iter().map(|&(_, y)| y).max()?;
+    let height = (y1 - y0 + 1) as usize;
+    let font = FONTS.iter().find(|font| font.height == height)?;
+
+    let lit = |x| (y0..=y1).any(|y| dots.contains(&(x, y)));
+    let mut text = String::new();
+    let mut x = x0;
+    while x <= x1 {
+        if !lit(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x <= x1 && lit(x) {
+            x += 1;
+        }
+        let rows: Vec<String> = (y0..=y1)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if dots.contains(&(x, y)) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        text.push(recognize(font, &rows)?);
+    }
+    Some(text)
+}
+
+/// The text drawn by the `true` cells of `grid`
+pub fn read_grid(grid: &Grid<bool>) -> Option<String> {
+    read(
+        grid.iter()
+            .filter(|(_, &lit)| lit)
+            .map(|((x, y), _)| (x as i64, y as i64)),
+    )
+}
+
+/// The letter drawn by `rows`, which have no blank column on either side
+fn recognize(font: &Font, rows: &[String]) -> Option<char> {
+    font.glyphs
+        .iter()
+        .find(|(_, glyph)| {
+            let lit = |column| glyph.iter().any(|row| row.as_bytes()[column] == b'#');
+            let width = glyph[0].len();
+            let start = (0..width).find(|&column| lit(column)).unwrap_or(0);
+            let end = (0..width).rfind(|&column| lit(column)).map_or(0, |i| i + 1);
+            glyph
+                .iter()
+                .zip(rows)
+                .all(|(glyph_row, row)| &glyph_row[start..end] == row)
+        })
+        .map(|&(letter, _)| letter)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Draw every letter of `font`, `gap` blank columns apart
+    fn specimen(font: &Font, gap: usize) -> String {
+        (0..font.height)
+            .map(|y| {
+                let row: Vec<_> = font.glyphs.iter().map(|(_, glyph)| glyph[y]).collect();
+                row.join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn dots(drawing: &str) -> Vec<SparsePos> {
+        let mut dots = vec![];
+        for (y, line) in drawing.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    dots.push((x as i64, y as i64));
+                }
+            }
+        }
+        dots
+    }
+
+    #[test]
+    fn test_fonts() {
+        for (font, gap) in [(&font::SMALL, 1), (&font::LARGE, 2)] {
+            let alphabet: String = font.glyphs.iter().map(|&(letter, _)| letter).collect();
+            assert_eq!(
+                read(dots(&specimen(font, gap))).as_deref(),
+                Some(&*alphabet)
+            );
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let drawing = "\
+            .#..#.####.###..\n\
+            .#..#.#....#..#.\n\
+            .####.###..#..#.\n\
+            .#..#.#....###..\n\
+            .#..#.#....#....\n\
+            .#..#.####.#....";
+        let shifted = dots(drawing).into_iter().map(|(x, y)| (x - 7, y + 3));
+        assert_eq!(read(shifted).as_deref(), Some("HEP"));
+        let grid = Grid::parse(drawing, |c| c == '#');
+        assert_eq!(read_grid(&grid).as_deref(), Some("HEP"));
+    }
+
+    #[test]
+    fn test_unreadable() {
+        assert_eq!(read(vec![]), None);
+        // a square is no letter, and 5 rows is no known font
+        assert_eq!(read(dots("###\n#.#\n###\n#.#\n###\n#.#")), None);
+        assert_eq!(read(dots("#\n#\n#\n#\n#")), None);
+    }
+}
@@ -0,0 +1,116 @@
+//! A seeded pseudo-random generator, to draw test cases and puzzle inputs
+//! which are the same on every run
+//!
+//! SplitMix64 is fast and good enough for that, not for anything needing
+//! unpredictable numbers.
+
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// In `0..bound`, which must not be empty
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "cannot draw below 0");
+        // rejecting the last partial run of `bound` values keeps it unbiased
+        let zone = u64::MAX - u64::MAX % bound as u64;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return (value % bound as u64) as usize;
+            }
+        }
+    }
+
+    /// In `range`, which must not be empty
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot draw in an empty range");
+        let width = end.abs_diff(start);
+        if width == u64::MAX {
+            return self.next_u64() as i64;
+        }
+        let zone = u64::MAX - u64::MAX % (width + 1);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return start.wrapping_add((value % (width + 1)) as i64);
+            }
+        }
+    }
+
+    /// True with probability `numerator / denominator`
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// A random element of `items`, which must not be empty
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Put `items` in a random order
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        // the reference outputs of SplitMix64 seeded with 1234567
+        let mut rng = Rng::new(1234567);
+        let expected = [
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+        let mut a = Rng::new(48);
+        let mut b = a.clone();
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let value = rng.range(-3..=3);
+            assert!((-3..=3).contains(&value));
+            seen[(value + 3) as usize] = true;
+            assert!(rng.below(5) < 5);
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(rng.range(4..=4), 4);
+        rng.range(i64::MIN..=i64::MAX);
+        assert!(!rng.chance(0, 3) && rng.chance(3, 3));
+
+        let mut items: Vec<_> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
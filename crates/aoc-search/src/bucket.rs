@@ -0,0 +1,96 @@
+use crate::{Found, Search, Stats, Visited};
+use std::hash::Hash;
+
+/// Dijkstra's search with a bucket queue, also known as Dial's algorithm
+///
+/// Costs are small integers, so the frontier is a list of buckets indexed by
+/// cost instead of a heap.
+pub fn dial<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    Search::new().dial(start, successors, is_goal)
+}
+
+impl Search {
+    pub fn dial<S, I>(
+        &self,
+        start: S,
+        mut successors: impl FnMut(&S) -> I,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Option<Found<S, usize>>
+    where
+        S: Clone + Eq + Hash,
+        I: IntoIterator<Item = (S, usize)>,
+    {
+        let mut buckets: Vec<Vec<usize>> = vec![vec![0]];
+        let mut visited = Visited::new(start, self.capacity);
+        let mut stats = Stats::default();
+        let mut cost = 0;
+        let mut pending = 1;
+        while pending > 0 {
+            while let Some(current) = buckets[cost].pop() {
+                pending -= 1;
+                // Important as we may have already found a better way
+                if cost > visited.nodes[current].cost {
+                    continue;
+                }
+                if is_goal(&visited.nodes[current].state) {
+                    return Some(visited.found(current, stats));
+                }
+                stats.expanded += 1;
+                for (next, step) in successors(&visited.nodes[current].state) {
+                    let next_cost = cost + step;
+                    if let Some(i) = visited.relax(next, next_cost, current) {
+                        stats.improved += 1;
+                        if buckets.len() <= next_cost {
+                            buckets.resize_with(next_cost + 1, Vec::new);
+                        }
+                        buckets[next_cost].push(i);
+                        pending += 1;
+                    }
+                }
+            }
+            cost += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dijkstra;
+    use crate::test::{check_path, successors};
+
+    #[test]
+    fn test_dial() {
+        for goal in [(2, 4), (2, 2), (4, 4), (0, 0)] {
+            let found = dial((0, 0), successors, |&pos| pos == goal).unwrap();
+            check_path(&found);
+            let expected = dijkstra((0, 0), successors, |&pos| pos == goal).unwrap();
+            assert_eq!(found.cost, expected.cost);
+        }
+        assert!(dial((0, 0), successors, |&pos| pos == (1, 1)).is_none());
+        let sized = Search::new().capacity(25);
+        let found = sized.dial((0, 0), successors, |&pos| pos == (4, 4));
+        assert_eq!(found.map(|found| found.cost), Some(8));
+    }
+
+    #[test]
+    fn test_zero_cost_moves() {
+        // 0 -> 1 costs nothing, 1 -> 2 costs 2, 0 -> 2 costs 3
+        let successors = |&s: &u8| match s {
+            0 => vec![(1, 0), (2, 3)],
+            1 => vec![(2, 2)],
+            _ => vec![],
+        };
+        let found = dial(0, successors, |&s| s == 2).unwrap();
+        assert_eq!((found.cost, found.path), (2, vec![0, 1, 2]));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{Cost, Found, Search, Stats, Visited};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+/// Cheapest path with non-negative move costs
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    Search::new().dijkstra(start, successors, is_goal)
+}
+
+/// Cheapest path, guided by a `heuristic` which must never overestimate the
+/// remaining cost to a goal
+///
+/// A state may be expanded again when a cheaper way to it shows up later,
+/// which only happens when the heuristic is not consistent.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    Search::new().astar(start, successors, heuristic, is_goal)
+}
+
+impl Search {
+    pub fn dijkstra<S, C, I>(
+        &self,
+        start: S,
+        successors: impl FnMut(&S) -> I,
+        is_goal: impl FnMut(&S) -> bool,
+    ) -> Option<Found<S, C>>
+    where
+        S: Clone + Eq + Hash,
+        C: Cost,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        self.astar(start, successors, |_| C::default(), is_goal)
+    }
+
+    pub fn astar<S, C, I>(
+        &self,
+        start: S,
+        mut successors: impl FnMut(&S) -> I,
+        mut heuristic: impl FnMut(&S) -> C,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Option<Found<S, C>>
+    where
+        S: Clone + Eq + Hash,
+        C: Cost,
+        I: IntoIterator<Item = (S, C)>,
+    {
+        let mut open = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+        let mut visited = Visited::new(start, self.capacity);
+        let mut stats = Stats::default();
+        while let Some(Reverse((_, cost, current))) = open.pop() {
+            // skip the entries superseded by a cheaper way
+            if cost > visited.nodes[current].cost {
+                continue;
+            }
+            if is_goal(&visited.nodes[current].state) {
+                return Some(visited.found(current, stats));
+            }
+            stats.expanded += 1;
+            for (next, step) in successors(&visited.nodes[current].state) {
+                let next_cost = cost + step;
+                let estimate = next_cost + heuristic(&next);
+                if let Some(i) = visited.relax(next, next_cost, current) {
+                    stats.improved += 1;
+                    open.push(Reverse((estimate, next_cost, i)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{check_path, manhattan, successors};
+
+    #[test]
+    fn test_dijkstra() {
+        let found = dijkstra((0, 0), successors, |&pos| pos == (2, 4)).unwrap();
+        // around the 9s
+        assert_eq!(found.cost, 6);
+        check_path(&found);
+        let found = dijkstra((0, 0), successors, |&pos| pos == (2, 2)).unwrap();
+        assert_eq!(found.cost, 16);
+        check_path(&found);
+        assert!(dijkstra((0, 0), successors, |&pos| pos == (1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_astar_expands_less() {
+        let goal = (4, 4);
+        let plain = dijkstra((0, 0), successors, |&pos| pos == goal).unwrap();
+        let guided = astar((0, 0), successors, manhattan(goal), |&pos| pos == goal).unwrap();
+        assert_eq!(plain.cost, 8);
+        assert_eq!(guided.cost, 8);
+        check_path(&guided);
+        assert!(guided.stats.expanded <= plain.stats.expanded);
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let found = astar(7u8, |_| [(8, 1u32)], |_| 0, |&s| s == 7).unwrap();
+        assert_eq!((found.cost, found.path), (0, vec![7]));
+        assert_eq!(found.stats, Stats::default());
+    }
+}
@@ -0,0 +1,283 @@
+//! Graph searches: breadth-first, Dijkstra and A*
+//!
+//! Graphs are implicit: a search starts from one state and asks a closure for
+//! the successors of every state it expands, together with the cost of each
+//! move. Every search returns the cost of the best path to a goal, the path
+//! itself and how much work it took.
+
+mod bucket;
+mod heap;
+
+pub use bucket::dial;
+pub use heap::{astar, dijkstra};
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Add;
+
+/// Path costs, `Default` being the cost of the empty path
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were generated
+    pub expanded: usize,
+    /// Successors reached with a better cost than before
+    pub improved: usize,
+}
+
+/// Settings shared by every search, the free functions use the defaults
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Search {
+    capacity: usize,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expected number of reached states, so that the tables of reached
+    /// states do not grow during big searches
+    pub fn capacity(self, capacity: usize) -> Self {
+        Self { capacity }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub cost: C,
+    /// From the start to the goal, both included
+    pub path: Vec<S>,
+    pub stats: Stats,
+}
+
+impl<S, C> Found<S, C> {
+    pub fn goal(&self) -> &S {
+        self.path.last().unwrap()
+    }
+}
+
+/// The multiply-rotate hash of rustc, much faster than the default SipHash on
+/// the small keys of puzzle states, which are not attacker controlled
+#[derive(Default)]
+struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for &byte in chunks.remainder() {
+            self.add(byte as u64);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Reached states, each with the best known cost and the state it came from
+///
+/// States are stored once and referred to by their index, so that the queues
+/// of the searches stay small.
+struct Visited<S, C> {
+    index: HashMap<S, usize, BuildHasherDefault<FxHasher>>,
+    nodes: Vec<Node<S, C>>,
+}
+
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    parent: Option<usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Visited<S, C> {
+    fn new(start: S, capacity: usize) -> Self {
+        let mut index = HashMap::with_capacity_and_hasher(capacity, Default::default());
+        index.insert(start.clone(), 0);
+        let mut nodes = Vec::with_capacity(capacity);
+        nodes.push(Node {
+            state: start,
+            cost: C::default(),
+            parent: None,
+        });
+        Self { index, nodes }
+    }
+
+    /// Record `state` reached from `parent` at `cost` unless it is already
+    /// known with a cost at least as good, returns its index if it was recorded
+    fn relax(&mut self, state: S, cost: C, parent: usize) -> Option<usize> {
+        match self.index.get(&state) {
+            Some(&i) if self.nodes[i].cost <= cost => None,
+            Some(&i) => {
+                self.nodes[i].cost = cost;
+                self.nodes[i].parent = Some(parent);
+                Some(i)
+            }
+            None => {
+                let i = self.nodes.len();
+                self.index.insert(state.clone(), i);
+                self.nodes.push(Node {
+                    state,
+                    cost,
+                    parent: Some(parent),
+                });
+                Some(i)
+            }
+        }
+    }
+
+    fn found(&self, goal: usize, stats: Stats) -> Found<S, C> {
+        let mut path = vec![];
+        let mut current = Some(goal);
+        while let Some(i) = current {
+            path.push(self.nodes[i].state.clone());
+            current = self.nodes[i].parent;
+        }
+        path.reverse();
+        Found {
+            cost: self.nodes[goal].cost,
+            path,
+            stats,
+        }
+    }
+}
+
+/// Breadth-first search, the cost being the number of moves
+pub fn bfs<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    Search::new().bfs(start, successors, is_goal)
+}
+
+impl Search {
+    pub fn bfs<S, I>(
+        &self,
+        start: S,
+        mut successors: impl FnMut(&S) -> I,
+        mut is_goal: impl FnMut(&S) -> bool,
+    ) -> Option<Found<S, usize>>
+    where
+        S: Clone + Eq + Hash,
+        I: IntoIterator<Item = S>,
+    {
+        let mut visited = Visited::new(start, self.capacity);
+        let mut queue = VecDeque::from([0]);
+        let mut stats = Stats::default();
+        while let Some(current) = queue.pop_front() {
+            if is_goal(&visited.nodes[current].state) {
+                return Some(visited.found(current, stats));
+            }
+            stats.expanded += 1;
+            let cost = visited.nodes[current].cost + 1;
+            for next in successors(&visited.nodes[current].state) {
+                if let Some(i) = visited.relax(next, cost, current) {
+                    stats.improved += 1;
+                    queue.push_back(i);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    /// A 5x5 maze, moving costs the digit of the destination, `#` are walls
+    pub const MAZE: [&str; 5] = ["11111", "1###1", "19991", "1#1#1", "11111"];
+
+    pub type Pos = (usize, usize);
+
+    pub fn successors(&(x, y): &Pos) -> Vec<(Pos, usize)> {
+        let mut result = vec![];
+        let candidates = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (x, y) in candidates {
+            if let Some(c) = MAZE.get(y).and_then(|row| row.as_bytes().get(x)) {
+                if *c != b'#' {
+                    result.push(((x, y), (c - b'0') as usize));
+                }
+            }
+        }
+        result
+    }
+
+    /// Every move costs at least one
+    pub fn manhattan(goal: Pos) -> impl Fn(&Pos) -> usize {
+        move |&(x, y)| x.abs_diff(goal.0) + y.abs_diff(goal.1)
+    }
+
+    /// Consecutive states of a path are neighbors and the costs add up
+    pub fn check_path(found: &Found<Pos, usize>) {
+        let cost: usize = found
+            .path
+            .windows(2)
+            .map(|w| {
+                successors(&w[0])
+                    .into_iter()
+                    .find(|(next, _)| *next == w[1])
+                    .unwrap()
+                    .1
+            })
+            .sum();
+        assert_eq!(cost, found.cost);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbors = |pos: &Pos| successors(pos).into_iter().map(|(next, _)| next);
+        let found = bfs((0, 0), neighbors, |&pos| pos == (2, 4)).unwrap();
+        assert_eq!(found.cost, 6);
+        assert_eq!(found.path.len(), 7);
+        assert_eq!(found.path[0], (0, 0));
+        assert_eq!(*found.goal(), (2, 4));
+        assert!(bfs((0, 0), neighbors, |&pos| pos == (1, 1)).is_none());
+    }
+}
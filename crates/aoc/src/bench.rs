@@ -0,0 +1,181 @@
+//! Repeated timing of the parse and part phases of a solution
+
+use aoc_core::{ParseError, Runner};
+use std::fmt::Write;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Timing {
+    /// Panics without samples
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+        Self {
+            median,
+            min: samples[0],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Benchmark {
+    pub year: u16,
+    pub day: u8,
+    pub runs: usize,
+    pub parse: Timing,
+    /// Solved parts only
+    pub parts: Vec<(u8, Timing)>,
+}
+
+/// Solve `raw` `runs` times after one warm-up run
+pub fn bench(
+    year: u16,
+    day: u8,
+    runner: Runner,
+    raw: &str,
+    runs: usize,
+) -> Result<Benchmark, ParseError> {
+    assert!(runs > 0, "at least one run is needed");
+    let warm_up = runner(raw, &[1, 2])?;
+    let solved: Vec<_> = warm_up
+        .answers
+        .iter()
+        .filter(|answer| answer.solved)
+        .map(|answer| answer.part)
+        .collect();
+
+    let mut parse = vec![];
+    let mut parts = vec![vec![]; solved.len()];
+    for _ in 0..runs {
+        let report = runner(raw, &solved)?;
+        parse.push(report.parse);
+        for (samples, answer) in parts.iter_mut().zip(report.answers) {
+            samples.push(answer.elapsed);
+        }
+    }
+    Ok(Benchmark {
+        year,
+        day,
+        runs,
+        parse: Timing::from_samples(parse),
+        parts: solved
+            .into_iter()
+            .zip(parts)
+            .map(|(part, samples)| (part, Timing::from_samples(samples)))
+            .collect(),
+    })
+}
+
+/// Serialize benchmarks with times in nanoseconds, one day per line so that
+/// results of two commits can be diffed
+pub fn to_json(benchmarks: &[Benchmark]) -> String {
+    let timing = |t: &Timing| {
+        format!(
+            "{{\"median_ns\": {}, \"min_ns\": {}}}",
+            t.median.as_nanos(),
+            t.min.as_nanos()
+        )
+    };
+    let mut json = String::from("[\n");
+    for (index, benchmark) in benchmarks.iter().enumerate() {
+        write!(
+            json,
+            "  {{\"year\": {}, \"day\": {}, \"runs\": {}, \"parse\": {}",
+            benchmark.year,
+            benchmark.day,
+            benchmark.runs,
+            timing(&benchmark.parse)
+        )
+        .unwrap();
+        for (part, t) in &benchmark.parts {
+            write!(json, ", \"part{}\": {}", part, timing(t)).unwrap();
+        }
+        json.push('}');
+        if index + 1 < benchmarks.len() {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+    json.push_str("]\n");
+    json
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_core::{Registry, Solution};
+    use std::fmt::Display;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+        const PART1: bool = true;
+
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(raw: &str) -> Result<Self::Input<'_>, ParseError> {
+            Ok(raw.lines().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> impl Display {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_timing() {
+        let ms = Duration::from_millis;
+        let timing = Timing::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!((timing.median, timing.min), (ms(3), ms(1)));
+        let timing = Timing::from_samples(vec![ms(4), ms(2), ms(8), ms(6)]);
+        assert_eq!((timing.median, timing.min), (ms(5), ms(2)));
+    }
+
+    #[test]
+    fn test_bench_skips_unsolved() {
+        let mut registry = Registry::new();
+        registry.register::<Lines>();
+        let benchmark = bench(2000, 1, registry.get(2000, 1).unwrap(), "a\nb", 3).unwrap();
+        assert_eq!(benchmark.runs, 3);
+        let parts: Vec<_> = benchmark.parts.iter().map(|(part, _)| *part).collect();
+        assert_eq!(parts, [1]);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let timing = Timing {
+            median: Duration::from_micros(2),
+            min: Duration::from_micros(1),
+        };
+        let benchmark = Benchmark {
+            year: 2021,
+            day: 6,
+            runs: 10,
+            parse: timing,
+            parts: vec![(1, timing)],
+        };
+        assert_eq!(
+            to_json(&[benchmark.clone(), benchmark]),
+            "[\n".to_string()
+                + "  {\"year\": 2021, \"day\": 6, \"runs\": 10, \
+                   \"parse\": {\"median_ns\": 2000, \"min_ns\": 1000}, \
+                   \"part1\": {\"median_ns\": 2000, \"min_ns\": 1000}},\n"
+                + "  {\"year\": 2021, \"day\": 6, \"runs\": 10, \
+                   \"parse\": {\"median_ns\": 2000, \"min_ns\": 1000}, \
+                   \"part1\": {\"median_ns\": 2000, \"min_ns\": 1000}}\n"
+                + "]\n"
+        );
+    }
+}
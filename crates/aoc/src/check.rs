@@ -0,0 +1,113 @@
+//! Running solutions against the expected answers of the `answers.txt` manifests
+
+use aoc_core::{ParseError, Registry};
+use aoc_input::answers::normalize;
+use aoc_input::{InputError, Resolver, Variant};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// The manifest has no answer for this part
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub variant: Variant,
+    /// The answer, or why the input could not be parsed
+    pub actual: Result<String, ParseError>,
+    pub expected: Option<String>,
+}
+
+impl Outcome {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.actual) {
+            (_, Err(_)) => Status::Fail,
+            (None, _) => Status::Missing,
+            (Some(expected), Ok(actual)) if normalize(expected) == normalize(actual) => {
+                Status::Pass
+            }
+            (Some(_), _) => Status::Fail,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} day {} part {} ({}): ",
+            self.year, self.day, self.part, self.variant
+        )?;
+        let actual = match &self.actual {
+            Ok(actual) => actual,
+            Err(err) => return write!(f, "invalid input: {}", err),
+        };
+        match (self.status(), &self.expected) {
+            (Status::Pass, _) => write!(f, "ok"),
+            (Status::Missing, _) => write!(f, "no expected answer, got {:?}", actual),
+            (Status::Fail, expected) => write!(
+                f,
+                "expected {:?}, got {:?}",
+                expected.as_deref().unwrap_or_default(),
+                actual
+            ),
+        }
+    }
+}
+
+/// Solve every day of `year` that has a `variant` input and compare the
+/// answers with the manifest. Days without such an input are skipped, as are
+/// unsolved parts the manifest does not mention. Both parts of a day fail
+/// when its input cannot be parsed.
+pub fn check(
+    registry: &Registry,
+    resolver: &Resolver,
+    year: u16,
+    variant: Variant,
+) -> Result<Vec<Outcome>, InputError> {
+    let answers = resolver.answers(year)?;
+    let mut outcomes = vec![];
+    for ((_, day), runner) in registry.iter().filter(|((y, _), _)| *y == year) {
+        let raw = match resolver.read(year, day, variant) {
+            Ok(raw) => raw,
+            Err(InputError::Missing { .. }) => continue,
+            Err(err) => return Err(err),
+        };
+        let expected = |part| answers.get(day, part, variant).map(str::to_owned);
+        let report = match runner(&raw, &[1, 2]) {
+            Ok(report) => report,
+            Err(err) => {
+                outcomes.extend([1, 2].map(|part| Outcome {
+                    year,
+                    day,
+                    part,
+                    variant,
+                    actual: Err(err.clone()),
+                    expected: expected(part),
+                }));
+                continue;
+            }
+        };
+        for answer in report.answers {
+            let expected = expected(answer.part);
+            if expected.is_none() && !answer.solved {
+                continue;
+            }
+            outcomes.push(Outcome {
+                year,
+                day,
+                part: answer.part,
+                variant,
+                actual: Ok(answer.value),
+                expected,
+            });
+        }
+    }
+    Ok(outcomes)
+}
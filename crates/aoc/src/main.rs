@@ -0,0 +1,632 @@
+use aoc::check::{check, Status};
+use aoc::solutions;
+use aoc_image::{Format, Recorder};
+use aoc_input::{InputError, Resolver, Variant};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: aoc run --year YEAR --day DAY [--part 1|2] [--example] [--data DIR] [--input PATH|-]
+               [--render IMAGE] [--frames DIR [--every N] [--frame-format FORMAT]]
+               [--scale N]
+       aoc verify [--year YEAR] [--example] [--data DIR]
+       aoc bench [--year YEAR] [--day DAY] [--example] [--data DIR] [--runs N] [--json PATH]
+       aoc generate --year YEAR --day DAY [--seed SEED] [--size N] [--output PATH]
+
+The input is looked up under DIR, $AOC_DATA or the repository, unless a PATH
+is given. `--input -` reads the input from stdin.
+
+`run --render` also draws the final state of the puzzles that have one to
+IMAGE, a .pbm, .pgm or .ppm file, with N×N pixels per cell (1 by default).
+`run --frames` replays the simulation of the puzzles that have one, writing
+every Nth step (every step by default) to DIR as text, or as images when
+FORMAT is pbm, pgm or ppm rather than txt.
+
+`verify` checks the answers of every day, or every day of YEAR, against the
+`answers.txt` manifests, on the real inputs and the examples or only on the
+examples with `--example`.
+
+`bench` times the parsing and each part of the selected days over N runs
+(10 by default) and reports the median and minimum, optionally saving them
+as JSON to PATH.
+
+`generate` writes a random input of size N for the puzzles that have a
+generator to PATH, or to stdout, the same SEED and N giving the same input.";
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_SCALE: usize = 1;
+const DEFAULT_EVERY: usize = 1;
+const DEFAULT_SEED: u64 = 1;
+
+enum Command {
+    Run(Options),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    Generate(GenerateOptions),
+}
+
+struct Options {
+    year: u16,
+    day: u8,
+    parts: Vec<u8>,
+    variant: Variant,
+    data: Option<String>,
+    input: Option<String>,
+    render: Option<String>,
+    frames: Option<FrameOptions>,
+    scale: usize,
+}
+
+struct FrameOptions {
+    dir: String,
+    every: usize,
+    /// Text frames when `None`
+    format: Option<Format>,
+}
+
+struct VerifyOptions {
+    year: Option<u16>,
+    variants: Vec<Variant>,
+    data: Option<String>,
+}
+
+struct BenchOptions {
+    year: Option<u16>,
+    day: Option<u8>,
+    variant: Variant,
+    data: Option<String>,
+    runs: usize,
+    json: Option<String>,
+}
+
+struct GenerateOptions {
+    year: u16,
+    day: u8,
+    seed: u64,
+    /// The default size of the generator when `None`
+    size: Option<usize>,
+    output: Option<String>,
+}
+
+/// Every flag of every command, validated per command by [`parse_args`]
+#[derive(Default)]
+struct Flags {
+    year: Option<u16>,
+    day: Option<u8>,
+    parts: Option<Vec<u8>>,
+    example: bool,
+    data: Option<String>,
+    input: Option<String>,
+    runs: Option<usize>,
+    json: Option<String>,
+    render: Option<String>,
+    frames: Option<String>,
+    every: Option<usize>,
+    frame_format: Option<Option<Format>>,
+    scale: Option<usize>,
+    seed: Option<u64>,
+    size: Option<usize>,
+    output: Option<String>,
+}
+
+impl Flags {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut flags = Self::default();
+        let mut iter = args.iter();
+        while let Some(flag) = iter.next() {
+            if flag == "--example" {
+                flags.example = true;
+                continue;
+            }
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("missing value for `{}`", flag))?;
+            let invalid = || format!("invalid value `{}` for `{}`", value, flag);
+            match flag.as_str() {
+                "--year" => flags.year = Some(value.parse().map_err(|_| invalid())?),
+                "--day" => flags.day = Some(value.parse().map_err(|_| invalid())?),
+                "--part" => match value.as_str() {
+                    "1" => flags.parts = Some(vec![1]),
+                    "2" => flags.parts = Some(vec![2]),
+                    _ => return Err(invalid()),
+                },
+                "--data" => flags.data = Some(value.clone()),
+                "--input" => flags.input = Some(value.clone()),
+                "--runs" => match value.parse() {
+                    Ok(runs) if runs > 0 => flags.runs = Some(runs),
+                    _ => return Err(invalid()),
+                },
+                "--json" => flags.json = Some(value.clone()),
+                "--render" => match Format::from_path(value) {
+                    Some(_) => flags.render = Some(value.clone()),
+                    None => return Err(invalid()),
+                },
+                "--frames" => flags.frames = Some(value.clone()),
+                "--every" => match value.parse() {
+                    Ok(every) if every > 0 => flags.every = Some(every),
+                    _ => return Err(invalid()),
+                },
+                "--frame-format" => match value.as_str() {
+                    "txt" => flags.frame_format = Some(None),
+                    _ => match Format::from_path(format!("frame.{}", value)) {
+                        Some(format) => flags.frame_format = Some(Some(format)),
+                        None => return Err(invalid()),
+                    },
+                },
+                "--scale" => match value.parse() {
+                    Ok(scale) if scale > 0 => flags.scale = Some(scale),
+                    _ => return Err(invalid()),
+                },
+                "--seed" => flags.seed = Some(value.parse().map_err(|_| invalid())?),
+                "--size" => flags.size = Some(value.parse().map_err(|_| invalid())?),
+                "--output" => flags.output = Some(value.clone()),
+                _ => return Err(format!("unknown flag `{}`", flag)),
+            }
+        }
+        Ok(flags)
+    }
+
+    /// Reject the flags given to `command` which it does not take
+    fn reject(&self, command: &str, flags: &[&str]) -> Result<(), String> {
+        let given = [
+            ("--day", self.day.is_some()),
+            ("--example", self.example),
+            ("--data", self.data.is_some()),
+            ("--part", self.parts.is_some()),
+            ("--input", self.input.is_some()),
+            ("--runs", self.runs.is_some()),
+            ("--json", self.json.is_some()),
+            ("--render", self.render.is_some()),
+            ("--frames", self.frames.is_some()),
+            ("--every", self.every.is_some()),
+            ("--frame-format", self.frame_format.is_some()),
+            ("--scale", self.scale.is_some()),
+            ("--seed", self.seed.is_some()),
+            ("--size", self.size.is_some()),
+            ("--output", self.output.is_some()),
+        ];
+        match given
+            .into_iter()
+            .find(|(flag, given)| *given && flags.contains(flag))
+        {
+            Some((flag, _)) => Err(format!("`{}` is not supported by {}", flag, command)),
+            None => Ok(()),
+        }
+    }
+
+    fn variant(&self) -> Variant {
+        if self.example {
+            Variant::Example
+        } else {
+            Variant::Input
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let command = match args.first().map(String::as_str) {
+        Some(command @ ("run" | "verify" | "bench" | "generate")) => command,
+        Some(command) => return Err(format!("unknown command `{}`", command)),
+        None => return Err("missing command".to_string()),
+    };
+    let flags = Flags::parse(&args[1..])?;
+    let variant = flags.variant();
+    match command {
+        "run" => {
+            flags.reject(
+                command,
+                &["--runs", "--json", "--seed", "--size", "--output"],
+            )?;
+            if flags.frames.is_none() {
+                if flags.every.is_some() {
+                    return Err("`--every` needs `--frames`".to_string());
+                }
+                if flags.frame_format.is_some() {
+                    return Err("`--frame-format` needs `--frames`".to_string());
+                }
+            }
+            let image_frames = matches!(flags.frame_format, Some(Some(_)));
+            if flags.scale.is_some() && flags.render.is_none() && !image_frames {
+                return Err("`--scale` needs `--render` or image frames".to_string());
+            }
+            let frames = flags.frames.map(|dir| FrameOptions {
+                dir,
+                every: flags.every.unwrap_or(DEFAULT_EVERY),
+                format: flags.frame_format.flatten(),
+            });
+            Ok(Command::Run(Options {
+                year: flags.year.ok_or("missing `--year`")?,
+                day: flags.day.ok_or("missing `--day`")?,
+                parts: flags.parts.unwrap_or_else(|| vec![1, 2]),
+                variant,
+                data: flags.data,
+                input: flags.input,
+                render: flags.render,
+                frames,
+                scale: flags.scale.unwrap_or(DEFAULT_SCALE),
+            }))
+        }
+        "verify" => {
+            flags.reject(
+                command,
+                &[
+                    "--day",
+                    "--part",
+                    "--input",
+                    "--runs",
+                    "--json",
+                    "--render",
+                    "--frames",
+                    "--every",
+                    "--frame-format",
+                    "--scale",
+                    "--seed",
+                    "--size",
+                    "--output",
+                ],
+            )?;
+            let variants = match variant {
+                Variant::Input => vec![Variant::Input, Variant::Example],
+                Variant::Example => vec![Variant::Example],
+            };
+            Ok(Command::Verify(VerifyOptions {
+                year: flags.year,
+                variants,
+                data: flags.data,
+            }))
+        }
+        "bench" => {
+            flags.reject(
+                command,
+                &[
+                    "--part",
+                    "--input",
+                    "--render",
+                    "--frames",
+                    "--every",
+                    "--frame-format",
+                    "--scale",
+                    "--seed",
+                    "--size",
+                    "--output",
+                ],
+            )?;
+            Ok(Command::Bench(BenchOptions {
+                year: flags.year,
+                day: flags.day,
+                variant,
+                data: flags.data,
+                runs: flags.runs.unwrap_or(DEFAULT_RUNS),
+                json: flags.json,
+            }))
+        }
+        _ => {
+            flags.reject(
+                command,
+                &[
+                    "--part",
+                    "--example",
+                    "--data",
+                    "--input",
+                    "--runs",
+                    "--json",
+                    "--render",
+                    "--frames",
+                    "--every",
+                    "--frame-format",
+                    "--scale",
+                ],
+            )?;
+            Ok(Command::Generate(GenerateOptions {
+                year: flags.year.ok_or("missing `--year`")?,
+                day: flags.day.ok_or("missing `--day`")?,
+                seed: flags.seed.unwrap_or(DEFAULT_SEED),
+                size: flags.size,
+                output: flags.output,
+            }))
+        }
+    }
+}
+
+fn resolver(data: &Option<String>) -> Resolver {
+    match data {
+        Some(root) => Resolver::with_root(root),
+        None => Resolver::from_env(),
+    }
+}
+
+fn read_input(options: &Options) -> Result<String, InputError> {
+    match options.input.as_deref() {
+        Some("-") => aoc_input::read_stdin(),
+        Some(path) => aoc_input::read_file(path),
+        None => resolver(&options.data).read(options.year, options.day, options.variant),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match parse_args(&args).unwrap_or_else(|message| fail(&message)) {
+        Command::Run(options) => run(&options),
+        Command::Verify(options) => verify(&options),
+        Command::Bench(options) => bench(&options),
+        Command::Generate(options) => generate(&options),
+    }
+}
+
+fn run(options: &Options) {
+    let solver = solutions::registry()
+        .get(options.year, options.day)
+        .unwrap_or_else(|| {
+            fail(&format!(
+                "no solution for {} day {}",
+                options.year, options.day
+            ))
+        });
+    let raw = read_input(options).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+
+    let report = solver(&raw, &options.parts).unwrap_or_else(|err| {
+        eprintln!(
+            "error: {} day {}: invalid input: {}",
+            options.year, options.day, err
+        );
+        process::exit(1);
+    });
+    println!("{} day {}", options.year, options.day);
+    println!("Parsed input ({})", format_duration(report.parse));
+    for answer in report.answers {
+        let elapsed = format_duration(answer.elapsed);
+        if answer.value.contains('\n') {
+            println!("Answer of p{}: ({})", answer.part, elapsed);
+            println!("{}", answer.value.trim_matches('\n'));
+        } else {
+            println!("Answer of p{}: {} ({})", answer.part, answer.value, elapsed);
+        }
+    }
+    if let Some(path) = &options.render {
+        render(options, &raw, path);
+    }
+    if let Some(frames) = &options.frames {
+        record(options, &raw, frames);
+    }
+}
+
+fn render(options: &Options, raw: &str, path: &str) {
+    let renderer = solutions::registry()
+        .renderer(options.year, options.day)
+        .expect("every solution has a renderer");
+    // the input was parsed successfully to solve the puzzle already
+    let image = match renderer(raw).expect("the input is valid") {
+        Some(image) => image,
+        None => {
+            eprintln!(
+                "error: {} day {} has nothing to render",
+                options.year, options.day
+            );
+            process::exit(1);
+        }
+    };
+    let image = image.scaled(options.scale);
+    if let Err(err) = image.save(path) {
+        eprintln!("error: cannot write {}: {}", path, err);
+        process::exit(1);
+    }
+    println!(
+        "Rendered {}×{} image to {}",
+        image.width(),
+        image.height(),
+        path
+    );
+}
+
+fn record(options: &Options, raw: &str, frames: &FrameOptions) {
+    let animator = solutions::registry()
+        .animator(options.year, options.day)
+        .expect("every solution has an animator");
+    let recorder = Recorder::new(&frames.dir, frames.every).unwrap_or_else(|err| {
+        eprintln!("error: cannot create {}: {}", frames.dir, err);
+        process::exit(1);
+    });
+    let mut recorder = match frames.format {
+        Some(format) => recorder.images(format, options.scale),
+        None => recorder,
+    };
+    if !animator(raw, &mut recorder).expect("the input is valid") {
+        eprintln!(
+            "error: {} day {} has no simulation to replay",
+            options.year, options.day
+        );
+        process::exit(1);
+    }
+    match recorder.finish() {
+        Ok(count) => println!("Recorded {} frames to {}", count, frames.dir),
+        Err(err) => {
+            eprintln!("error: cannot write frames to {}: {}", frames.dir, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn verify(options: &VerifyOptions) {
+    let registry = solutions::registry();
+    let resolver = resolver(&options.data);
+    let years: BTreeSet<_> = registry
+        .iter()
+        .map(|((year, _), _)| year)
+        .filter(|year| options.year.is_none_or(|y| y == *year))
+        .collect();
+    if years.is_empty() {
+        fail(&format!(
+            "no solution for {}",
+            options.year.unwrap_or_default()
+        ));
+    }
+
+    let mut counts = [0; 3];
+    println!("year  day  part  variant  status   answer");
+    for year in years {
+        for &variant in &options.variants {
+            let outcomes = check(&registry, &resolver, year, variant).unwrap_or_else(|err| {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            });
+            for outcome in outcomes {
+                let actual = match &outcome.actual {
+                    Ok(actual) => summarize(actual),
+                    Err(err) => format!("invalid input: {}", err),
+                };
+                let (index, status, detail) = match outcome.status() {
+                    Status::Pass => (0, "pass", actual),
+                    Status::Fail if outcome.actual.is_err() => (1, "FAIL", actual),
+                    Status::Fail => (
+                        1,
+                        "FAIL",
+                        format!(
+                            "expected {}, got {}",
+                            summarize(outcome.expected.as_deref().unwrap_or_default()),
+                            actual
+                        ),
+                    ),
+                    Status::Missing => (2, "missing", actual),
+                };
+                counts[index] += 1;
+                println!(
+                    "{:<4}  {:>3}  {:>4}  {:<7}  {:<7}  {}",
+                    outcome.year, outcome.day, outcome.part, outcome.variant, status, detail
+                );
+            }
+        }
+    }
+    let [passed, failed, missing] = counts;
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn bench(options: &BenchOptions) {
+    let registry = solutions::registry();
+    let resolver = resolver(&options.data);
+    let selected: Vec<_> = registry
+        .iter()
+        .filter(|((year, day), _)| {
+            options.year.is_none_or(|y| y == *year) && options.day.is_none_or(|d| d == *day)
+        })
+        .collect();
+    if selected.is_empty() {
+        fail("no solution matches the selection");
+    }
+
+    let mut benchmarks = vec![];
+    println!("year  day  phase      median        min");
+    for ((year, day), runner) in selected {
+        let raw = match resolver.read(year, day, options.variant) {
+            Ok(raw) => raw,
+            Err(InputError::Missing { .. }) => continue,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        };
+        let benchmark =
+            aoc::bench::bench(year, day, runner, &raw, options.runs).unwrap_or_else(|err| {
+                eprintln!("error: {} day {}: invalid input: {}", year, day, err);
+                process::exit(1);
+            });
+        let phases = [("parse".to_string(), benchmark.parse)].into_iter().chain(
+            benchmark
+                .parts
+                .iter()
+                .map(|(part, t)| (format!("part{}", part), *t)),
+        );
+        for (phase, timing) in phases {
+            println!(
+                "{:<4}  {:>3}  {:<6}  {:>9}  {:>9}",
+                year,
+                day,
+                phase,
+                format_duration(timing.median),
+                format_duration(timing.min)
+            );
+        }
+        benchmarks.push(benchmark);
+    }
+    if let Some(path) = &options.json {
+        if let Err(err) = fs::write(path, aoc::bench::to_json(&benchmarks)) {
+            eprintln!("error: cannot write {}: {}", path, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn generate(options: &GenerateOptions) {
+    let generator = aoc_gen::generator(options.year, options.day).unwrap_or_else(|| {
+        let available: Vec<_> = aoc_gen::GENERATORS
+            .iter()
+            .map(|g| format!("{} day {}", g.year, g.day))
+            .collect();
+        fail(&format!(
+            "no generator for {} day {}, only for {}",
+            options.year,
+            options.day,
+            available.join(", ")
+        ))
+    });
+    let size = options.size.unwrap_or(generator.default_size);
+    if !generator.sizes.contains(&size) {
+        fail(&format!(
+            "the size of {} day {} counts {} and must be in {}..={}",
+            options.year,
+            options.day,
+            generator.unit,
+            generator.sizes.start(),
+            generator.sizes.end()
+        ));
+    }
+    let input = generator.generate(options.seed, size);
+    match &options.output {
+        Some(path) => {
+            if let Err(err) = fs::write(path, &input) {
+                eprintln!("error: cannot write {}: {}", path, err);
+                process::exit(1);
+            }
+            eprintln!(
+                "Generated {} {} for {} day {} to {}",
+                size, generator.unit, options.year, options.day, path
+            );
+        }
+        None => print!("{}", input),
+    }
+}
+
+/// Keep the table on one line per answer
+fn summarize(answer: &str) -> String {
+    let lines = answer.trim_matches('\n').lines().count();
+    if lines > 1 {
+        format!("<{} lines>", lines)
+    } else {
+        answer.to_string()
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{}µs", micros)
+    } else {
+        format!("{:.2}ms", micros as f64 / 1000.0)
+    }
+}
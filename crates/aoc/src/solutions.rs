@@ -0,0 +1,24 @@
+use aoc_core::Registry;
+
+/// Every solution known to the runner
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register::<a2019_day1::Day1>();
+    registry.register::<a2019_day2::Day2>();
+    registry.register::<a2019_day3::Day3>();
+    registry.register::<a2019_day4::Day4>();
+    registry.register::<a2019_day5::Day5>();
+    registry.register::<a2019_day6::Day6>();
+    registry.register::<a2019_day7::Day7>();
+    registry.register::<a2019_day8::Day8>();
+    registry.register::<a2019_day9::Day9>();
+    registry.register::<a2019_day10::Day10>();
+    registry.register::<a2019_day11::Day11>();
+    registry.register::<a2019_day12::Day12>();
+    registry.register::<a2019_day13::Day13>();
+    registry.register::<a2019_day14::Day14>();
+    registry.register::<a2019_day15::Day15>();
+    a2020::register(&mut registry);
+    a2021::register(&mut registry);
+    registry
+}
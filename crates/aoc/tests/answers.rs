@@ -0,0 +1,36 @@
+use aoc::check::{check, Status};
+use aoc::solutions;
+use aoc_input::{Resolver, Variant};
+use std::collections::BTreeSet;
+
+/// Check every year having a manifest, reporting all mismatches at once
+fn check_all(variant: Variant) {
+    let registry = solutions::registry();
+    let resolver = Resolver::from_env();
+    let years: BTreeSet<_> = registry.iter().map(|((year, _), _)| year).collect();
+    let mut checked = 0;
+    let mut failures = vec![];
+    for year in years {
+        if !resolver.answers_path(year).is_file() {
+            continue;
+        }
+        for outcome in check(&registry, &resolver, year, variant).unwrap() {
+            checked += 1;
+            if outcome.status() != Status::Pass {
+                failures.push(outcome.to_string());
+            }
+        }
+    }
+    assert!(checked > 0, "no {} answers checked", variant);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn examples() {
+    check_all(Variant::Example);
+}
+
+#[test]
+fn inputs() {
+    check_all(Variant::Input);
+}
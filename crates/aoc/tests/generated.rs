@@ -0,0 +1,23 @@
+use aoc::solutions;
+use aoc_gen::GENERATORS;
+
+/// Every generated input is accepted and solved, from the smallest size on
+#[test]
+fn generated_inputs_are_solved() {
+    let registry = solutions::registry();
+    let mut failures = vec![];
+    for generator in &GENERATORS {
+        let runner = registry.get(generator.year, generator.day).unwrap();
+        let smallest = *generator.sizes.start();
+        for (seed, size) in [(0, smallest), (1, smallest + 1), (2, smallest + 8)] {
+            let raw = generator.generate(seed, size);
+            if let Err(err) = runner(&raw, &[1, 2]) {
+                failures.push(format!(
+                    "{} day {}, seed {}, size {}: {}",
+                    generator.year, generator.day, seed, size, err
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
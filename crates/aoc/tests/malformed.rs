@@ -0,0 +1,164 @@
+use aoc::solutions;
+use std::collections::BTreeSet;
+
+/// A malformed input of every day and the line its error should point at
+fn malformed() -> Vec<((u16, u8), String, usize)> {
+    let cases = [
+        ((2019, 1), "12\n14\n1969x\n", 3),
+        ((2019, 2), "1,9,10,3,2,3,11,0,99,30,40,x50\n", 1),
+        ((2019, 3), "R8,U5,L5,D3\nU7,R6,X4,L4\n", 2),
+        ((2019, 4), "123456-x\n", 1),
+        ((2019, 5), "3,0,4,0,99,-\n", 1),
+        ((2019, 6), "COM)B\nB)C\nC-D\n", 3),
+        ((2019, 7), "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0,?\n", 1),
+        ((2019, 8), "0222112222120003\n", 1),
+        ((2019, 9), "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99,O\n", 1),
+        ((2019, 10), ".#..#\n.....\n#####\n....#\n...x#\n", 5),
+        ((2019, 11), "3,8,1005,8,x\n", 1),
+        (
+            (2019, 12),
+            "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=4, y=-8>\n<x=3, y=5, z=-1>\n",
+            3,
+        ),
+        ((2019, 13), "1,380,379,385,1008,2267,610381,q\n", 1),
+        (
+            (2019, 14),
+            "10 ORE => 10 A\n1 ORE => 1 B\n7 A, 1 B => 1 C\n7 A, 1 C => FUEL\n",
+            4,
+        ),
+        ((2019, 14), "10 ORE => 0 FUEL\n", 1),
+        ((2019, 14), "0 ORE => 1 FUEL\n", 1),
+        ((2019, 14), "1 B => 1 A\n1 A => 1 B\n1 A => 1 FUEL\n", 2),
+        ((2019, 15), "3,1033,1008,1033,1,1032,1005,y\n", 1),
+        ((2019, 15), "3,100,104,0,1105,1,0\n", 1),
+        ((2020, 1), "1721\n979\n36x\n", 3),
+        ((2020, 1), "", 1),
+        ((2020, 1), "1721\n979\n", 1),
+        ((2020, 1), "1010\n1010\n", 1),
+        ((2020, 1), "1721\n2021\n", 2),
+        ((2020, 2), "1-3 a: abcde\n1-3 b: cdefg\n2-9 c ccccccccc\n", 3),
+        (
+            (2020, 3),
+            "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.?\n",
+            4,
+        ),
+        (
+            (2020, 4),
+            "ecl:gry pid:860033327\nbyr:1937\n\niyr:2013 ecl\nhcl:#cfa07d\n",
+            4,
+        ),
+        ((2020, 5), "FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRL\nBBFFBBFRLX\n", 4),
+        ((2020, 5), "", 1),
+        ((2020, 5), "FBFBBFFRLR\nFBFBBFFRRL\n", 1),
+        ((2020, 6), "abc\n\na\nb\nc\n\nab\nA\n", 8),
+        ((2021, 1), "199\n200\n20x\n", 3),
+        ((2021, 2), "forward 5\ndown 5\nsideways 8\n", 3),
+        ((2021, 3), "00100\n11110\n10110\n1011\n", 4),
+        ((2021, 3), "", 1),
+        ((2021, 3), "101010101010101010101010101010101\n", 1),
+        (
+            (2021, 4),
+            "7,4,9\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 1x 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n",
+            5,
+        ),
+        ((2021, 5), "0,9 -> 5,9\n8,0 -> 0,8\n9,4 3,4\n", 3),
+        ((2021, 6), "3,4,3,1,2,x", 1),
+        ((2021, 7), "16,1,2,0,4,2,7,1,2,a", 1),
+        (
+            (2021, 8),
+            "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe\n\
+             edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec fcgedb cgb dgebacf gc\n",
+            2,
+        ),
+        ((2021, 9), "2199943210\n3987894921\n98567898x2\n", 3),
+        (
+            (2021, 10),
+            "[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n{([(<{}[<>[]}>{[]{[(<()x\n",
+            3,
+        ),
+        ((2021, 10), "", 1),
+        ((2021, 10), "[]\n{()()()>\n", 1),
+        ((2021, 10), "[(\n[]]\n", 2),
+        ((2021, 11), "5483143223\n2745854711\n52645611x8\n", 3),
+        ((2021, 12), "start-A\nstart-b\nA-c\nA_b\n", 4),
+        ((2021, 12), "", 1),
+        ((2021, 12), "start-A\nA-b\n", 1),
+        ((2021, 12), "start-A\nA-B\nB-end\n", 2),
+        ((2021, 13), "6,10\n0,14\n\nfold along y=7\nfold along z=5\n", 5),
+        ((2021, 13), "6,10\n0,14\n\n", 4),
+        ((2021, 13), "6,10\n0,14\n\nfold along y=7\nfold along x=2\n", 5),
+        ((2021, 14), "NNCB\n\nCH -> B\nHH -> N\nCB - H\n", 5),
+        ((2021, 15), "1163751742\n1381373672\n213651x361\n", 3),
+        ((2021, 15), "", 1),
+        ((2021, 15), "1163\n1301\n", 2),
+        ((2021, 16), "8A004A801", 1),
+        ((2021, 16), "0600A48C210842108401246108421084200", 1),
+        ((2021, 17), "target area: x=20..30, z=-10..-5", 1),
+        ((2021, 18), "[[1,2],[3,4]]\n[1,[2,3]\n", 2),
+        ((2021, 18), "", 1),
+        (
+            (2021, 19),
+            "--- scanner 0 ---\n404,-588,-901\n528,-643,409\n\n--- scanner 1 ---\n686,422\n",
+            6,
+        ),
+        ((2021, 19), "", 1),
+        (
+            (2021, 19),
+            "--- scanner 0 ---\n0,0,0\n1,0,0\n\n--- scanner 1 ---\n5,5,5\n",
+            5,
+        ),
+        ((2021, 21), "Player 1 starting position: 4\nPlayer 2 starting position: 11\n", 2),
+        (
+            (2021, 22),
+            "on x=10..12,y=10..12,z=10..12\non x=11..13,y=11..13,z=11..13\noff x=9..11,y=9..11\n",
+            3,
+        ),
+        (
+            (2021, 23),
+            "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#E#\n  #########\n",
+            4,
+        ),
+        ((2021, 24), "inp w\nmul x 0\nadd x q\n", 3),
+        ((2021, 25), "v...>>.vv>\n.vv>>.vv..\n>>.>v>...x\n", 3),
+    ];
+    let mut cases: Vec<_> = cases
+        .into_iter()
+        .map(|(key, raw, line)| (key, raw.to_string(), line))
+        .collect();
+    // the enhancement algorithm is too long to be written out
+    cases.push((
+        (2021, 20),
+        format!("{}\n\n#..#.\n#....\n##.x#\n", "#".repeat(512)),
+        5,
+    ));
+    cases
+}
+
+#[test]
+fn every_day_is_covered() {
+    let covered: BTreeSet<_> = malformed().into_iter().map(|(key, _, _)| key).collect();
+    let missing: Vec<_> = solutions::registry()
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| !covered.contains(key))
+        .collect();
+    assert!(missing.is_empty(), "no malformed input for {:?}", missing);
+}
+
+#[test]
+fn errors_point_at_the_offending_line() {
+    let registry = solutions::registry();
+    let mut failures = vec![];
+    for ((year, day), raw, line) in malformed() {
+        let runner = registry.get(year, day).unwrap();
+        match runner(&raw, &[]) {
+            Ok(_) => failures.push(format!("{} day {}: parsed", year, day)),
+            Err(err) if err.line != line => failures.push(format!(
+                "{} day {}: expected line {}, got {}",
+                year, day, line, err
+            )),
+            Err(_) => {}
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}